@@ -8,7 +8,7 @@ use serde::{Deserialize, Serialize};
 use crate::core::{ContractAddress, GlobalRoot};
 use crate::hash::StarkHash;
 use crate::serde_utils::{BytesAsHex, PrefixedBytesAsHex};
-use crate::transaction::{Transaction, TransactionOutput};
+use crate::transaction::{ExecutionResources, PriceUnit, Transaction, TransactionOutput};
 
 /// A block.
 #[derive(Debug, Default, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
@@ -18,7 +18,12 @@ pub struct Block {
 }
 
 /// The header of a [Block](`crate::block::Block`).
+///
+/// `gas_price` is the L1 gas price in Wei, as in headers from before gas prices in STRK, and
+/// equals `l1_gas_price.price_in_wei`. A header that has only one of them is deserialized with
+/// the other one derived from it.
 #[derive(Debug, Default, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[serde(from = "RawBlockHeader")]
 pub struct BlockHeader {
     // TODO: Consider removing the block hash from the header (note it can be computed from
     // the rest of the fields.
@@ -26,10 +31,92 @@ pub struct BlockHeader {
     pub parent_hash: BlockHash,
     pub block_number: BlockNumber,
     pub gas_price: GasPrice,
+    pub l1_gas_price: GasPricePerToken,
+    pub l1_data_gas_price: GasPricePerToken,
     pub state_root: GlobalRoot,
     pub sequencer: ContractAddress,
     pub timestamp: BlockTimestamp,
-    // TODO: add missing commitments.
+    pub l1_da_mode: L1DataAvailabilityMode,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_commitment: Option<TransactionCommitment>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_commitment: Option<EventCommitment>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_diff_commitment: Option<StateDiffCommitment>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receipt_commitment: Option<ReceiptCommitment>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n_transactions: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n_events: Option<usize>,
+    pub starknet_version: StarknetVersion,
+}
+
+// The header as serialized before and after gas prices in STRK. The fields that were added after
+// the first version of the header are optional.
+#[derive(Deserialize)]
+struct RawBlockHeader {
+    block_hash: BlockHash,
+    parent_hash: BlockHash,
+    block_number: BlockNumber,
+    #[serde(default)]
+    gas_price: Option<GasPrice>,
+    #[serde(default)]
+    l1_gas_price: Option<GasPricePerToken>,
+    #[serde(default)]
+    l1_data_gas_price: GasPricePerToken,
+    state_root: GlobalRoot,
+    sequencer: ContractAddress,
+    timestamp: BlockTimestamp,
+    #[serde(default)]
+    l1_da_mode: L1DataAvailabilityMode,
+    #[serde(default)]
+    transaction_commitment: Option<TransactionCommitment>,
+    #[serde(default)]
+    event_commitment: Option<EventCommitment>,
+    #[serde(default)]
+    state_diff_commitment: Option<StateDiffCommitment>,
+    #[serde(default)]
+    receipt_commitment: Option<ReceiptCommitment>,
+    #[serde(default)]
+    n_transactions: Option<usize>,
+    #[serde(default)]
+    n_events: Option<usize>,
+    #[serde(default)]
+    starknet_version: StarknetVersion,
+}
+
+impl From<RawBlockHeader> for BlockHeader {
+    fn from(raw: RawBlockHeader) -> Self {
+        let (gas_price, l1_gas_price) = match (raw.gas_price, raw.l1_gas_price) {
+            (gas_price, Some(l1_gas_price)) => {
+                (gas_price.unwrap_or(l1_gas_price.price_in_wei), l1_gas_price)
+            }
+            (gas_price, None) => {
+                let gas_price = gas_price.unwrap_or_default();
+                (gas_price, GasPricePerToken { price_in_wei: gas_price, ..Default::default() })
+            }
+        };
+        Self {
+            block_hash: raw.block_hash,
+            parent_hash: raw.parent_hash,
+            block_number: raw.block_number,
+            gas_price,
+            l1_gas_price,
+            l1_data_gas_price: raw.l1_data_gas_price,
+            state_root: raw.state_root,
+            sequencer: raw.sequencer,
+            timestamp: raw.timestamp,
+            l1_da_mode: raw.l1_da_mode,
+            transaction_commitment: raw.transaction_commitment,
+            event_commitment: raw.event_commitment,
+            state_diff_commitment: raw.state_diff_commitment,
+            receipt_commitment: raw.receipt_commitment,
+            n_transactions: raw.n_transactions,
+            n_events: raw.n_events,
+            starknet_version: raw.starknet_version,
+        }
+    }
 }
 
 /// The [transactions](`crate::transaction::Transaction`) and their
@@ -40,6 +127,13 @@ pub struct BlockBody {
     pub transaction_outputs: Vec<TransactionOutput>,
 }
 
+impl BlockBody {
+    /// Returns the total resources used by the transactions of the block.
+    pub fn execution_resources(&self) -> ExecutionResources {
+        self.transaction_outputs.iter().map(TransactionOutput::execution_resources).sum()
+    }
+}
+
 /// The status of a [Block](`crate::block::Block`).
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
 pub enum BlockStatus {
@@ -128,6 +222,116 @@ impl From<GasPrice> for PrefixedBytesAsHex<16_usize> {
     }
 }
 
+/// A gas price at a [Block](`crate::block::Block`), in each of the fee tokens.
+#[derive(
+    Debug, Copy, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord,
+)]
+pub struct GasPricePerToken {
+    pub price_in_fri: GasPrice,
+    pub price_in_wei: GasPrice,
+}
+
+impl GasPricePerToken {
+    /// Returns the gas price in the given unit.
+    pub fn get(&self, unit: PriceUnit) -> GasPrice {
+        match unit {
+            PriceUnit::Wei => self.price_in_wei,
+            PriceUnit::Fri => self.price_in_fri,
+        }
+    }
+}
+
+/// The mode in which a [Block](`crate::block::Block`) publishes its state diff on L1.
+#[derive(
+    Debug, Copy, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord,
+)]
+pub enum L1DataAvailabilityMode {
+    /// The state diff is published as calldata.
+    #[serde(rename = "CALLDATA")]
+    #[default]
+    Calldata,
+    /// The state diff is published in a blob.
+    #[serde(rename = "BLOB")]
+    Blob,
+}
+
+/// The commitment on the [transactions](`crate::transaction::Transaction`) of a
+/// [Block](`crate::block::Block`).
+#[derive(
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Hash,
+    Deserialize,
+    Serialize,
+    PartialOrd,
+    Ord,
+    Display,
+)]
+pub struct TransactionCommitment(pub StarkHash);
+
+/// The commitment on the [events](`crate::transaction::Event`) of a [Block](`crate::block::Block`).
+#[derive(
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Hash,
+    Deserialize,
+    Serialize,
+    PartialOrd,
+    Ord,
+    Display,
+)]
+pub struct EventCommitment(pub StarkHash);
+
+/// The commitment on the [state diff](`crate::state::StateDiff`) of a
+/// [Block](`crate::block::Block`).
+#[derive(
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Hash,
+    Deserialize,
+    Serialize,
+    PartialOrd,
+    Ord,
+    Display,
+)]
+pub struct StateDiffCommitment(pub StarkHash);
+
+/// The commitment on the [receipts](`crate::transaction::TransactionReceipt`) of a
+/// [Block](`crate::block::Block`).
+#[derive(
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Hash,
+    Deserialize,
+    Serialize,
+    PartialOrd,
+    Ord,
+    Display,
+)]
+pub struct ReceiptCommitment(pub StarkHash);
+
+/// The version of Starknet that produced a [Block](`crate::block::Block`), e.g. "0.13.1".
+#[derive(
+    Debug, Default, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord, Display,
+)]
+pub struct StarknetVersion(pub String);
+
 /// The timestamp of a [Block](`crate::block::Block`).
 #[derive(
     Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord,
@@ -0,0 +1,203 @@
+//! Commitments on the content of a [Block](`crate::block::Block`).
+//!
+//! A commitment is the root of a binary Merkle-Patricia tree of height 64, whose leaves are keyed
+//! by their index in the block. The tree is hashed with Pedersen before Starknet v0.13.2 and with
+//! Poseidon from it.
+#[cfg(test)]
+#[path = "block_commitment_test.rs"]
+mod block_commitment_test;
+
+use starknet_crypto::FieldElement;
+
+use crate::block::{BlockBody, EventCommitment, TransactionCommitment};
+use crate::hash::{
+    pedersen_hash, pedersen_hash_array, poseidon_hash_many, HashFunction, Pedersen, Poseidon,
+    StarkFelt, StarkHash,
+};
+use crate::transaction::{
+    DeclareTransaction, DeployAccountTransaction, Event, InvokeTransaction, Transaction,
+    TransactionHash,
+};
+
+/// The height of the Patricia tree of a block commitment.
+pub const COMMITMENT_TREE_HEIGHT: u8 = 64;
+
+/// A hash function of block commitments, which also defines how the leaves are hashed.
+pub trait CommitmentHashFunction: HashFunction {
+    /// Hashes a transaction hash and the signature of the transaction, if it has one.
+    fn transaction_leaf(
+        transaction_hash: &TransactionHash,
+        signature: Option<&[StarkFelt]>,
+    ) -> StarkHash;
+    /// Hashes an event and the hash of the transaction that emitted it.
+    fn event_leaf(event: &Event, transaction_hash: &TransactionHash) -> StarkHash;
+}
+
+impl CommitmentHashFunction for Pedersen {
+    fn transaction_leaf(
+        transaction_hash: &TransactionHash,
+        signature: Option<&[StarkFelt]>,
+    ) -> StarkHash {
+        pedersen_hash(&transaction_hash.0, &pedersen_hash_array(signature.unwrap_or_default()))
+    }
+
+    // The transaction hash is not part of the leaf.
+    fn event_leaf(event: &Event, _transaction_hash: &TransactionHash) -> StarkHash {
+        let keys = event.content.keys.iter().map(|key| key.0).collect::<Vec<_>>();
+        pedersen_hash_array(&[
+            *event.from_address.0.key(),
+            pedersen_hash_array(&keys),
+            pedersen_hash_array(&event.content.data.0),
+        ])
+    }
+}
+
+impl CommitmentHashFunction for Poseidon {
+    // A transaction without a signature, or with an empty one, is hashed as if its signature was
+    // [0].
+    fn transaction_leaf(
+        transaction_hash: &TransactionHash,
+        signature: Option<&[StarkFelt]>,
+    ) -> StarkHash {
+        let zero = [StarkFelt::from(0_u8)];
+        let signature = match signature {
+            Some(signature) if !signature.is_empty() => signature,
+            _ => &zero,
+        };
+        let elements = [&[transaction_hash.0], signature].concat();
+        poseidon_hash_many(&elements)
+    }
+
+    fn event_leaf(event: &Event, transaction_hash: &TransactionHash) -> StarkHash {
+        let keys = &event.content.keys;
+        let data = &event.content.data.0;
+        let elements = [*event.from_address.0.key(), transaction_hash.0, len_as_felt(keys.len())]
+            .into_iter()
+            .chain(keys.iter().map(|key| key.0))
+            .chain([len_as_felt(data.len())])
+            .chain(data.iter().copied())
+            .collect::<Vec<_>>();
+        poseidon_hash_many(&elements)
+    }
+}
+
+fn len_as_felt(len: usize) -> StarkFelt {
+    StarkFelt::from(len as u64)
+}
+
+/// Calculates the commitment on the transactions of a block. The leaf of each transaction is the
+/// hash of the transaction hash and its signature.
+pub fn calculate_transaction_commitment<H: CommitmentHashFunction>(
+    transactions: &[Transaction],
+) -> TransactionCommitment {
+    let leaves = transactions
+        .iter()
+        .map(|transaction| {
+            H::transaction_leaf(&transaction.transaction_hash(), transaction_signature(transaction))
+        })
+        .collect::<Vec<_>>();
+    TransactionCommitment(calculate_commitment_root::<H>(&leaves))
+}
+
+/// Calculates the commitment on the transactions of a block from before Starknet v0.11.1, in
+/// which only the signatures of invoke transactions are part of the leaves.
+pub fn calculate_deprecated_transaction_commitment(
+    transactions: &[Transaction],
+) -> TransactionCommitment {
+    let leaves = transactions
+        .iter()
+        .map(|transaction| {
+            let signature = match transaction {
+                Transaction::Invoke(_) => transaction_signature(transaction),
+                _ => None,
+            };
+            Pedersen::transaction_leaf(&transaction.transaction_hash(), signature)
+        })
+        .collect::<Vec<_>>();
+    TransactionCommitment(calculate_commitment_root::<Pedersen>(&leaves))
+}
+
+/// Calculates the commitment on the events emitted in a block, ordered by transaction and by
+/// their index in the transaction output.
+pub fn calculate_event_commitment<H: CommitmentHashFunction>(body: &BlockBody) -> EventCommitment {
+    let leaves = body
+        .transactions
+        .iter()
+        .zip(&body.transaction_outputs)
+        .flat_map(|(transaction, output)| {
+            let transaction_hash = transaction.transaction_hash();
+            output.events().iter().map(move |event| H::event_leaf(event, &transaction_hash))
+        })
+        .collect::<Vec<_>>();
+    EventCommitment(calculate_commitment_root::<H>(&leaves))
+}
+
+/// Calculates the root of a commitment tree whose leaves are given in the order of their indices.
+/// The root of an empty tree is 0.
+pub fn calculate_commitment_root<H: HashFunction>(leaves: &[StarkHash]) -> StarkHash {
+    let indexed_leaves =
+        leaves.iter().enumerate().map(|(index, leaf)| (index as u64, *leaf)).collect::<Vec<_>>();
+    match calculate_subtree::<H>(&indexed_leaves, COMMITMENT_TREE_HEIGHT) {
+        Some(subtree) => subtree.hash::<H>(),
+        None => StarkFelt::from(0_u8),
+    }
+}
+
+fn transaction_signature(transaction: &Transaction) -> Option<&[StarkFelt]> {
+    match transaction {
+        Transaction::Declare(DeclareTransaction::V0(tx) | DeclareTransaction::V1(tx)) => {
+            Some(&tx.signature.0)
+        }
+        Transaction::Declare(DeclareTransaction::V2(tx)) => Some(&tx.signature.0),
+        Transaction::Declare(DeclareTransaction::V3(tx)) => Some(&tx.signature.0),
+        Transaction::DeployAccount(DeployAccountTransaction::V1(tx)) => Some(&tx.signature.0),
+        Transaction::DeployAccount(DeployAccountTransaction::V3(tx)) => Some(&tx.signature.0),
+        Transaction::Invoke(InvokeTransaction::V0(tx)) => Some(&tx.signature.0),
+        Transaction::Invoke(InvokeTransaction::V1(tx)) => Some(&tx.signature.0),
+        Transaction::Invoke(InvokeTransaction::V3(tx)) => Some(&tx.signature.0),
+        Transaction::Deploy(_) | Transaction::L1Handler(_) => None,
+    }
+}
+
+// A non-empty subtree: an edge of `length` bits along `path` that ends in a node with hash
+// `bottom`. An edge of length 0 is the bottom node itself.
+struct SubTree {
+    bottom: StarkHash,
+    path: u64,
+    length: u8,
+}
+
+impl SubTree {
+    fn hash<H: HashFunction>(&self) -> StarkHash {
+        if self.length == 0 {
+            return self.bottom;
+        }
+        let hash = FieldElement::from(H::hash(&self.bottom, &StarkFelt::from(self.path)))
+            + FieldElement::from(self.length);
+        StarkFelt::from(hash)
+    }
+}
+
+// Leaves must be sorted by their index.
+fn calculate_subtree<H: HashFunction>(leaves: &[(u64, StarkHash)], height: u8) -> Option<SubTree> {
+    let (_, first_leaf) = leaves.first()?;
+    if height == 0 {
+        return Some(SubTree { bottom: *first_leaf, path: 0, length: 0 });
+    }
+    let split = leaves.partition_point(|(index, _)| (index >> (height - 1)) & 1 == 0);
+    let (left, right) = leaves.split_at(split);
+    match (calculate_subtree::<H>(left, height - 1), calculate_subtree::<H>(right, height - 1)) {
+        (Some(left), Some(right)) => Some(SubTree {
+            bottom: H::hash(&left.hash::<H>(), &right.hash::<H>()),
+            path: 0,
+            length: 0,
+        }),
+        (Some(left), None) => Some(SubTree { length: left.length + 1, ..left }),
+        (None, Some(right)) => Some(SubTree {
+            path: right.path | (1 << right.length),
+            length: right.length + 1,
+            bottom: right.bottom,
+        }),
+        (None, None) => None,
+    }
+}
@@ -0,0 +1,184 @@
+use starknet_crypto::FieldElement;
+
+use crate::block::BlockBody;
+use crate::block_commitment::{
+    calculate_commitment_root, calculate_deprecated_transaction_commitment,
+    calculate_event_commitment, calculate_transaction_commitment, CommitmentHashFunction,
+};
+use crate::core::{ContractAddress, PatriciaKey};
+use crate::hash::{pedersen_hash, pedersen_hash_array, Pedersen, Poseidon, StarkFelt, StarkHash};
+use crate::transaction::{
+    DeployAccountTransaction, DeployAccountTransactionV1, Event, EventContent, EventData, EventKey,
+    InvokeTransaction, InvokeTransactionOutput, InvokeTransactionV1, L1HandlerTransaction,
+    L1HandlerTransactionOutput, Transaction, TransactionHash, TransactionOutput,
+    TransactionSignature,
+};
+use crate::{patricia_key, stark_felt};
+
+fn edge_hash(bottom: StarkHash, length: u8) -> StarkHash {
+    StarkFelt::from(
+        FieldElement::from(pedersen_hash(&bottom, &stark_felt!("0x0")))
+            + FieldElement::from(length),
+    )
+}
+
+#[test]
+fn empty_commitment_root() {
+    assert_eq!(calculate_commitment_root::<Pedersen>(&[]), stark_felt!("0x0"));
+}
+
+#[test]
+fn commitment_root() {
+    let leaves = [stark_felt!("0x1"), stark_felt!("0x2"), stark_felt!("0x3")];
+    // Leaves 0 and 1 meet at a binary node, and leaf 2 is the bottom of an edge of length 1.
+    let bottom_node =
+        pedersen_hash(&pedersen_hash(&leaves[0], &leaves[1]), &edge_hash(leaves[2], 1));
+    assert_eq!(calculate_commitment_root::<Pedersen>(&leaves), edge_hash(bottom_node, 62));
+}
+
+#[test]
+fn transaction_commitment() {
+    let transactions = vec![
+        Transaction::Invoke(InvokeTransaction::V1(InvokeTransactionV1 {
+            transaction_hash: TransactionHash(stark_felt!("0x1234")),
+            signature: TransactionSignature(vec![stark_felt!("0x1"), stark_felt!("0x2")]),
+            ..Default::default()
+        })),
+        Transaction::L1Handler(L1HandlerTransaction {
+            transaction_hash: TransactionHash(stark_felt!("0x5678")),
+            ..Default::default()
+        }),
+    ];
+    let leaves = [
+        pedersen_hash(
+            &stark_felt!("0x1234"),
+            &pedersen_hash_array(&[stark_felt!("0x1"), stark_felt!("0x2")]),
+        ),
+        pedersen_hash(&stark_felt!("0x5678"), &pedersen_hash_array(&[])),
+    ];
+    assert_eq!(
+        calculate_transaction_commitment::<Pedersen>(&transactions).0,
+        edge_hash(pedersen_hash(&leaves[0], &leaves[1]), 63)
+    );
+}
+
+#[test]
+fn deprecated_transaction_commitment() {
+    let transactions = vec![Transaction::DeployAccount(DeployAccountTransaction::V1(
+        DeployAccountTransactionV1 {
+            transaction_hash: TransactionHash(stark_felt!("0x1234")),
+            signature: TransactionSignature(vec![stark_felt!("0x1"), stark_felt!("0x2")]),
+            ..Default::default()
+        },
+    ))];
+    // Only the signatures of invoke transactions are part of the leaves.
+    let leaf = pedersen_hash(&stark_felt!("0x1234"), &pedersen_hash_array(&[]));
+    assert_eq!(calculate_deprecated_transaction_commitment(&transactions).0, edge_hash(leaf, 64));
+    assert_ne!(
+        calculate_deprecated_transaction_commitment(&transactions),
+        calculate_transaction_commitment::<Pedersen>(&transactions)
+    );
+}
+
+#[test]
+fn event_commitment() {
+    let event = Event {
+        from_address: ContractAddress(patricia_key!("0x10")),
+        content: EventContent {
+            keys: vec![EventKey(stark_felt!("0x11"))],
+            data: EventData(vec![stark_felt!("0x12"), stark_felt!("0x13")]),
+        },
+    };
+    let body = BlockBody {
+        transactions: vec![
+            Transaction::L1Handler(L1HandlerTransaction::default()),
+            Transaction::L1Handler(L1HandlerTransaction::default()),
+        ],
+        transaction_outputs: vec![
+            TransactionOutput::Invoke(InvokeTransactionOutput::default()),
+            TransactionOutput::Invoke(InvokeTransactionOutput {
+                events: vec![event],
+                ..Default::default()
+            }),
+        ],
+    };
+    let leaf = pedersen_hash_array(&[
+        stark_felt!("0x10"),
+        pedersen_hash_array(&[stark_felt!("0x11")]),
+        pedersen_hash_array(&[stark_felt!("0x12"), stark_felt!("0x13")]),
+    ]);
+    assert_eq!(calculate_event_commitment::<Pedersen>(&body).0, edge_hash(leaf, 64));
+}
+
+#[test]
+fn poseidon_commitment_root() {
+    let leaves = [stark_felt!("0x1"), stark_felt!("0x2"), stark_felt!("0x3")];
+    assert_eq!(
+        calculate_commitment_root::<Poseidon>(&leaves[..1]),
+        stark_felt!("0x7752582c54a42fe0fa35c40f07293bb7d8efe90e21d8d2c06a7db52d7d9b7e1")
+    );
+    assert_eq!(
+        calculate_commitment_root::<Poseidon>(&leaves[..2]),
+        stark_felt!("0x1c1ba983ee0a0de87d87d67ea3cbee7023aa65f6b7bcf71259f122ea3af80bf")
+    );
+    assert_eq!(
+        calculate_commitment_root::<Poseidon>(&leaves),
+        stark_felt!("0x3b5cc7f1292eb3847c3f902d048a7e5dc7702d1c191ccd17c2d33f797e6fc32")
+    );
+}
+
+#[test]
+fn poseidon_transaction_commitment() {
+    let transaction = Transaction::Invoke(InvokeTransaction::V1(InvokeTransactionV1 {
+        transaction_hash: TransactionHash(stark_felt!("0x1")),
+        signature: TransactionSignature(vec![stark_felt!("0x2"), stark_felt!("0x3")]),
+        ..Default::default()
+    }));
+    assert_eq!(
+        calculate_transaction_commitment::<Poseidon>(&[transaction.clone(), transaction]).0,
+        stark_felt!("0x282b635972328bd1cfa86496fe920d20bd9440cd78ee8dc90ae2b383d664dcf")
+    );
+    // A transaction without a signature, or with an empty one, is hashed as if its signature was
+    // [0].
+    let unsigned_leaf =
+        stark_felt!("0xa93bf5e58b9378d093aa86ddc2f61a3295a1d1e665bd0ef3384dd07b30e033");
+    assert_eq!(
+        Poseidon::transaction_leaf(&TransactionHash(stark_felt!("0x1")), None),
+        unsigned_leaf
+    );
+    assert_eq!(
+        Poseidon::transaction_leaf(&TransactionHash(stark_felt!("0x1")), Some(&[])),
+        unsigned_leaf
+    );
+}
+
+#[test]
+fn poseidon_event_commitment() {
+    let event = |seed: u8| Event {
+        from_address: ContractAddress::try_from(StarkFelt::from(seed + 8)).unwrap(),
+        content: EventContent {
+            keys: vec![EventKey(StarkFelt::from(seed)), EventKey(StarkFelt::from(seed + 1))],
+            data: EventData((seed + 2..seed + 5).map(StarkFelt::from).collect()),
+        },
+    };
+    let transaction_hash = TransactionHash(stark_felt!("0x1234"));
+    assert_eq!(
+        Poseidon::event_leaf(&event(2), &transaction_hash),
+        stark_felt!("0x367807f532742a4dcbe2d8a47b974b22dd7496faa75edc64a3a5fdb6709057")
+    );
+
+    let body = BlockBody {
+        transactions: vec![Transaction::L1Handler(L1HandlerTransaction {
+            transaction_hash,
+            ..Default::default()
+        })],
+        transaction_outputs: vec![TransactionOutput::L1Handler(L1HandlerTransactionOutput {
+            events: vec![event(0), event(1), event(2)],
+            ..Default::default()
+        })],
+    };
+    assert_eq!(
+        calculate_event_commitment::<Poseidon>(&body).0,
+        stark_felt!("0x69bb140ddbbeb01d81c7201ecfb933031306e45dab9c77ff9f9ba3cd4c2b9c3")
+    );
+}
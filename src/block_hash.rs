@@ -0,0 +1,179 @@
+#[cfg(test)]
+#[path = "block_hash_test.rs"]
+mod block_hash_test;
+
+use crate::block::{
+    Block, BlockBody, BlockHash, BlockHeader, BlockNumber, EventCommitment, StarknetVersion,
+    TransactionCommitment,
+};
+use crate::block_commitment::{
+    calculate_deprecated_transaction_commitment, calculate_event_commitment,
+    calculate_transaction_commitment,
+};
+use crate::core::{ChainId, ContractAddress};
+use crate::hash::{pedersen_hash_array, Pedersen, StarkFelt};
+use crate::StarknetApiError;
+
+/// The sequencer address that is part of the hash of the mainnet blocks from Starknet v0.7.0 whose
+/// header has no sequencer address.
+pub const MAINNET_FALLBACK_SEQUENCER_ADDRESS: &str =
+    "0x21f4b90b0377c82bf330b7b5295820769e72d79d8acd0effa0ebde6e9988bc5";
+
+/// The formula used to calculate the hash of a [Block](`crate::block::Block`).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum BlockHashVersion {
+    /// Blocks before Starknet v0.7.0, whose hash depends on the chain id instead of the sequencer,
+    /// the timestamp and the events.
+    Pre0_7,
+    /// Blocks from Starknet v0.7.0 and before v0.11.1, whose transaction commitment only includes
+    /// the signatures of invoke transactions.
+    Pre0_11_1,
+    /// Blocks from Starknet v0.11.1.
+    Post0_11_1,
+}
+
+impl BlockHashVersion {
+    /// Returns the formula of the hash of the block with the given header on the given chain.
+    pub fn of_header(header: &BlockHeader, chain_id: &ChainId) -> Self {
+        let first_0_7_block = match chain_id.0.as_str() {
+            "SN_MAIN" => BlockNumber(833),
+            "SN_GOERLI" => BlockNumber(47028),
+            _ => BlockNumber(0),
+        };
+        if header.block_number < first_0_7_block {
+            BlockHashVersion::Pre0_7
+        } else if is_before(&header.starknet_version, &[0, 11, 1]) {
+            BlockHashVersion::Pre0_11_1
+        } else {
+            BlockHashVersion::Post0_11_1
+        }
+    }
+}
+
+// Returns true if the version is before `other`. A missing version is before any version.
+pub(crate) fn is_before(version: &StarknetVersion, other: &[u64]) -> bool {
+    let parts = version
+        .0
+        .split('.')
+        .map(|part| part.parse::<u64>().unwrap_or_default())
+        .collect::<Vec<_>>();
+    parts.as_slice() < other
+}
+
+/// The commitments of a [BlockBody](`crate::block::BlockBody`) that are part of the block hash.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct BlockBodyCommitments {
+    pub n_transactions: usize,
+    pub transaction_commitment: TransactionCommitment,
+    pub n_events: usize,
+    pub event_commitment: EventCommitment,
+}
+
+/// Calculates the transaction and event commitments of a block body, as defined for blocks
+/// hashed with the given formula.
+pub fn calculate_block_body_commitments(
+    body: &BlockBody,
+    version: BlockHashVersion,
+) -> BlockBodyCommitments {
+    let transaction_commitment = match version {
+        BlockHashVersion::Pre0_7 | BlockHashVersion::Pre0_11_1 => {
+            calculate_deprecated_transaction_commitment(&body.transactions)
+        }
+        BlockHashVersion::Post0_11_1 => {
+            calculate_transaction_commitment::<Pedersen>(&body.transactions)
+        }
+    };
+    BlockBodyCommitments {
+        n_transactions: body.transactions.len(),
+        transaction_commitment,
+        n_events: body.transaction_outputs.iter().map(|output| output.events().len()).sum(),
+        event_commitment: calculate_event_commitment::<Pedersen>(body),
+    }
+}
+
+/// Calculates the hash of a block from its header and body commitments.
+pub fn calculate_block_hash(
+    header: &BlockHeader,
+    commitments: &BlockBodyCommitments,
+    version: BlockHashVersion,
+    chain_id: &ChainId,
+) -> Result<BlockHash, StarknetApiError> {
+    let zero = StarkFelt::from(0_u8);
+    let n_transactions = StarkFelt::from(commitments.n_transactions as u64);
+    let block_hash = match version {
+        BlockHashVersion::Pre0_7 => pedersen_hash_array(&[
+            StarkFelt::from(header.block_number.0),
+            header.state_root.0,
+            // The sequencer address and the timestamp were not part of the hash.
+            zero,
+            zero,
+            n_transactions,
+            commitments.transaction_commitment.0,
+            // The events were not part of the hash.
+            zero,
+            zero,
+            // Protocol version and extra data.
+            zero,
+            zero,
+            StarkFelt::try_from(chain_id.as_hex().as_str())?,
+            header.parent_hash.0,
+        ]),
+        BlockHashVersion::Pre0_11_1 | BlockHashVersion::Post0_11_1 => pedersen_hash_array(&[
+            StarkFelt::from(header.block_number.0),
+            header.state_root.0,
+            *header.sequencer.0.key(),
+            StarkFelt::from(header.timestamp.0),
+            n_transactions,
+            commitments.transaction_commitment.0,
+            StarkFelt::from(commitments.n_events as u64),
+            commitments.event_commitment.0,
+            // Protocol version and extra data.
+            zero,
+            zero,
+            header.parent_hash.0,
+        ]),
+    };
+    Ok(BlockHash(block_hash))
+}
+
+/// Returns true if the counts and commitments in the header of the block match its body, and the
+/// hash in the header matches the hash calculated with the formula of the block on the given
+/// chain.
+pub fn verify_block_hash(block: &Block, chain_id: &ChainId) -> Result<bool, StarknetApiError> {
+    let header = &block.header;
+    let version = BlockHashVersion::of_header(header, chain_id);
+    let commitments = calculate_block_body_commitments(&block.body, version);
+    if !header_matches_commitments(header, &commitments) {
+        return Ok(false);
+    }
+    if calculate_block_hash(header, &commitments, version, chain_id)? == header.block_hash {
+        return Ok(true);
+    }
+
+    // Some mainnet blocks from Starknet v0.7.0 have no sequencer address in their header, but
+    // their hash was calculated with the address of the sequencer of the chain.
+    if version == BlockHashVersion::Pre0_7
+        || header.sequencer != ContractAddress::default()
+        || chain_id.0 != "SN_MAIN"
+    {
+        return Ok(false);
+    }
+    let header = BlockHeader {
+        sequencer: ContractAddress::try_from(StarkFelt::try_from(
+            MAINNET_FALLBACK_SEQUENCER_ADDRESS,
+        )?)?,
+        ..header.clone()
+    };
+    Ok(calculate_block_hash(&header, &commitments, version, chain_id)? == header.block_hash)
+}
+
+// Returns true if the counts and the commitments in the header, if any, match the commitments
+// of the body.
+fn header_matches_commitments(header: &BlockHeader, commitments: &BlockBodyCommitments) -> bool {
+    header.n_transactions.unwrap_or(commitments.n_transactions) == commitments.n_transactions
+        && header.n_events.unwrap_or(commitments.n_events) == commitments.n_events
+        && header.transaction_commitment.unwrap_or(commitments.transaction_commitment)
+            == commitments.transaction_commitment
+        && header.event_commitment.unwrap_or(commitments.event_commitment)
+            == commitments.event_commitment
+}
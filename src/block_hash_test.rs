@@ -0,0 +1,140 @@
+use crate::block::{
+    Block, BlockBody, BlockHeader, BlockNumber, BlockTimestamp, StarknetVersion,
+    TransactionCommitment,
+};
+use crate::block_hash::{
+    calculate_block_body_commitments, calculate_block_hash, verify_block_hash,
+    BlockBodyCommitments, BlockHashVersion,
+};
+use crate::core::{ChainId, ContractAddress, GlobalRoot, PatriciaKey};
+use crate::hash::{StarkFelt, StarkHash};
+use crate::test_utils::read_json_file;
+use crate::transaction::{
+    Event, EventContent, EventData, EventKey, InvokeTransaction, InvokeTransactionOutput,
+    InvokeTransactionV1, Transaction, TransactionHash, TransactionOutput, TransactionSignature,
+};
+use crate::{patricia_key, stark_felt};
+
+fn block() -> Block {
+    let transaction = Transaction::Invoke(InvokeTransaction::V1(InvokeTransactionV1 {
+        transaction_hash: TransactionHash(stark_felt!("0x1234")),
+        signature: TransactionSignature(vec![stark_felt!("0x1"), stark_felt!("0x2")]),
+        ..Default::default()
+    }));
+    let output = TransactionOutput::Invoke(InvokeTransactionOutput {
+        events: vec![Event {
+            from_address: ContractAddress(patricia_key!("0x10")),
+            content: EventContent {
+                keys: vec![EventKey(stark_felt!("0x11"))],
+                data: EventData(vec![stark_felt!("0x12"), stark_felt!("0x13")]),
+            },
+        }],
+        ..Default::default()
+    });
+    Block {
+        header: BlockHeader {
+            block_number: BlockNumber(1000),
+            state_root: GlobalRoot(stark_felt!("0x5")),
+            sequencer: ContractAddress(patricia_key!("0x6")),
+            timestamp: BlockTimestamp(1_650_000_000),
+            ..Default::default()
+        },
+        body: BlockBody { transactions: vec![transaction], transaction_outputs: vec![output] },
+    }
+}
+
+fn read_block(block_number: u64) -> Block {
+    serde_json::from_value(read_json_file(&format!("block_hash/block_{block_number}.json")))
+        .unwrap()
+}
+
+#[test]
+fn body_commitments() {
+    let commitments = calculate_block_body_commitments(&block().body, BlockHashVersion::Post0_11_1);
+    assert_eq!(commitments.n_transactions, 1);
+    assert_eq!(commitments.n_events, 1);
+    assert_eq!(
+        calculate_block_body_commitments(&BlockBody::default(), BlockHashVersion::Post0_11_1),
+        BlockBodyCommitments::default()
+    );
+}
+
+#[test]
+fn block_hash_version() {
+    let chain_id = ChainId("SN_MAIN".to_string());
+    let mut header = BlockHeader { block_number: BlockNumber(832), ..Default::default() };
+    assert_eq!(BlockHashVersion::of_header(&header, &chain_id), BlockHashVersion::Pre0_7);
+    header.block_number = BlockNumber(833);
+    assert_eq!(BlockHashVersion::of_header(&header, &chain_id), BlockHashVersion::Pre0_11_1);
+    header.starknet_version = StarknetVersion("0.11.0.2".to_string());
+    assert_eq!(BlockHashVersion::of_header(&header, &chain_id), BlockHashVersion::Pre0_11_1);
+    header.starknet_version = StarknetVersion("0.11.1".to_string());
+    assert_eq!(BlockHashVersion::of_header(&header, &chain_id), BlockHashVersion::Post0_11_1);
+
+    let header = BlockHeader::default();
+    let chain_id = ChainId("SN_SEPOLIA".to_string());
+    assert_eq!(BlockHashVersion::of_header(&header, &chain_id), BlockHashVersion::Pre0_11_1);
+}
+
+// The blocks were taken from Starknet mainnet. Block 2242 has no sequencer address in its header.
+#[test]
+fn verify_mainnet_block_hashes() {
+    let chain_id = ChainId("SN_MAIN".to_string());
+    for (block_number, version) in [
+        (832, BlockHashVersion::Pre0_7),
+        (867, BlockHashVersion::Pre0_11_1),
+        (887, BlockHashVersion::Pre0_11_1),
+        (2242, BlockHashVersion::Pre0_11_1),
+        (183862, BlockHashVersion::Post0_11_1),
+    ] {
+        let block = read_block(block_number);
+        assert_eq!(BlockHashVersion::of_header(&block.header, &chain_id), version);
+        assert!(verify_block_hash(&block, &chain_id).unwrap(), "Block {block_number}");
+    }
+    // The hash of blocks before Starknet v0.7.0 depends on the chain.
+    assert!(!verify_block_hash(&read_block(832), &ChainId("SN_GOERLI".to_string())).unwrap());
+}
+
+#[test]
+fn verify_block_hash_checks_header_commitments() {
+    let chain_id = ChainId("SN_MAIN".to_string());
+    let block = read_block(867);
+
+    let mut wrong_count = block.clone();
+    wrong_count.header.n_events = Some(block.header.n_events.unwrap() + 1);
+    assert!(!verify_block_hash(&wrong_count, &chain_id).unwrap());
+
+    let mut wrong_commitment = block.clone();
+    wrong_commitment.header.transaction_commitment = Some(TransactionCommitment::default());
+    assert!(!verify_block_hash(&wrong_commitment, &chain_id).unwrap());
+
+    // The counts and the commitments are part of the hash, so a header without them, such as a
+    // header from the feeder gateway, can still be verified.
+    let mut without_commitments = block.clone();
+    without_commitments.header.transaction_commitment = None;
+    without_commitments.header.event_commitment = None;
+    without_commitments.header.n_transactions = None;
+    without_commitments.header.n_events = None;
+    assert!(verify_block_hash(&without_commitments, &chain_id).unwrap());
+
+    let mut wrong_body = block;
+    wrong_body.body.transactions.swap(0, 1);
+    assert!(!verify_block_hash(&wrong_body, &chain_id).unwrap());
+}
+
+#[test]
+fn block_hash_depends_on_parent() {
+    let chain_id = ChainId("SN_MAIN".to_string());
+    let block = block();
+    let commitments = calculate_block_body_commitments(&block.body, BlockHashVersion::Post0_11_1);
+    let hash =
+        calculate_block_hash(&block.header, &commitments, BlockHashVersion::Post0_11_1, &chain_id)
+            .unwrap();
+    let mut header = block.header;
+    header.parent_hash.0 = StarkHash::from(1_u8);
+    assert_ne!(
+        hash,
+        calculate_block_hash(&header, &commitments, BlockHashVersion::Post0_11_1, &chain_id)
+            .unwrap()
+    );
+}
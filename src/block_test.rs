@@ -1,4 +1,12 @@
-use crate::block::BlockNumber;
+use serde_json::json;
+
+use crate::block::{
+    BlockBody, BlockHeader, BlockNumber, GasPrice, GasPricePerToken, L1DataAvailabilityMode,
+    StarknetVersion, StateDiffCommitment, TransactionCommitment,
+};
+use crate::hash::StarkFelt;
+use crate::stark_felt;
+use crate::transaction::{ExecutionResources, InvokeTransactionOutput, TransactionOutput};
 
 #[test]
 fn test_block_number_iteration() {
@@ -20,3 +28,87 @@ fn test_block_number_iteration() {
 
     assert_eq!(expected, from_iter);
 }
+
+#[test]
+fn block_header_serde() {
+    let header = BlockHeader {
+        gas_price: GasPrice(0x2),
+        l1_gas_price: GasPricePerToken {
+            price_in_fri: GasPrice(0x10),
+            price_in_wei: GasPrice(0x2),
+        },
+        l1_data_gas_price: GasPricePerToken {
+            price_in_fri: GasPrice(0x20),
+            price_in_wei: GasPrice(0x1),
+        },
+        l1_da_mode: L1DataAvailabilityMode::Blob,
+        transaction_commitment: Some(TransactionCommitment(stark_felt!("0x123"))),
+        state_diff_commitment: Some(StateDiffCommitment(stark_felt!("0x456"))),
+        n_transactions: Some(3),
+        starknet_version: StarknetVersion("0.13.1".to_string()),
+        ..Default::default()
+    };
+    let value = serde_json::to_value(&header).unwrap();
+    assert_eq!(value["gas_price"], json!("0x2"));
+    assert_eq!(value["l1_gas_price"], json!({"price_in_fri": "0x10", "price_in_wei": "0x2"}));
+    assert_eq!(value["l1_data_gas_price"], json!({"price_in_fri": "0x20", "price_in_wei": "0x1"}));
+    assert_eq!(value["l1_da_mode"], json!("BLOB"));
+    assert_eq!(value["transaction_commitment"], json!("0x123"));
+    assert_eq!(value["n_transactions"], json!(3));
+    assert_eq!(value["starknet_version"], json!("0.13.1"));
+    assert!(value.get("event_commitment").is_none());
+    assert!(value.get("n_events").is_none());
+    assert!(value.get("receipt_commitment").is_none());
+    assert_eq!(header, serde_json::from_value(value).unwrap());
+}
+
+#[test]
+fn block_header_without_new_fields_deserialization() {
+    let mut value = serde_json::to_value(BlockHeader::default()).unwrap();
+    for field in ["l1_data_gas_price", "l1_da_mode", "transaction_commitment", "starknet_version"] {
+        value.as_object_mut().unwrap().remove(field);
+    }
+    let header: BlockHeader = serde_json::from_value(value).unwrap();
+    assert_eq!(header, BlockHeader::default());
+}
+
+#[test]
+fn block_header_legacy_gas_price_deserialization() {
+    // A header as serialized before gas prices in STRK.
+    let json = json!({
+        "block_hash": "0x1",
+        "parent_hash": "0x2",
+        "block_number": 3,
+        "gas_price": "0x4",
+        "state_root": "0x5",
+        "sequencer": "0x6",
+        "timestamp": 7,
+    });
+    let header: BlockHeader = serde_json::from_value(json).unwrap();
+    assert_eq!(header.block_number, BlockNumber(3));
+    assert_eq!(header.gas_price, GasPrice(0x4));
+    assert_eq!(
+        header.l1_gas_price,
+        GasPricePerToken { price_in_fri: GasPrice(0), price_in_wei: GasPrice(0x4) }
+    );
+    assert_eq!(header.starknet_version, StarknetVersion::default());
+
+    // A header with a gas price per token only.
+    let mut value = serde_json::to_value(&header).unwrap();
+    value.as_object_mut().unwrap().remove("gas_price");
+    assert_eq!(serde_json::from_value::<BlockHeader>(value).unwrap(), header);
+}
+
+#[test]
+fn block_execution_resources() {
+    let output = |steps| {
+        TransactionOutput::Invoke(InvokeTransactionOutput {
+            execution_resources: ExecutionResources { steps, ..Default::default() },
+            ..Default::default()
+        })
+    };
+    let body =
+        BlockBody { transaction_outputs: vec![output(10), output(32)], ..Default::default() };
+    assert_eq!(body.execution_resources().steps, 42);
+    assert_eq!(BlockBody::default().execution_resources(), ExecutionResources::default());
+}
@@ -0,0 +1,277 @@
+//! The hash of a contract class, as defined in
+//! <https://docs.starknet.io/documentation/architecture_and_concepts/Smart_Contracts/class-hash/>.
+#[cfg(test)]
+#[path = "class_hash_test.rs"]
+mod class_hash_test;
+
+use once_cell::sync::Lazy;
+use serde_json::{Map, Value};
+use starknet_crypto::FieldElement;
+
+use crate::core::ClassHash;
+use crate::deprecated_contract_class::{
+    ContractClass as DeprecatedContractClass, ContractClassAbiEntry,
+    EntryPointType as DeprecatedEntryPointType,
+};
+use crate::hash::{pedersen_hash_array, poseidon_hash_many, starknet_keccak, StarkFelt, StarkHash};
+use crate::state::{ContractClass, EntryPointType};
+use crate::StarknetApiError;
+
+/// The version of the Sierra contract classes, which is part of their hash.
+pub const CONTRACT_CLASS_VERSION: &str = "CONTRACT_CLASS_V0.1.0";
+
+static CONTRACT_CLASS_VERSION_FELT: Lazy<StarkFelt> = Lazy::new(|| {
+    StarkFelt::from(
+        FieldElement::from_byte_slice_be(CONTRACT_CLASS_VERSION.as_bytes())
+            .expect("The contract class version should fit in a FieldElement."),
+    )
+});
+
+/// Calculates the hash of a Sierra (Cairo 1) contract class.
+pub fn calculate_class_hash(class: &ContractClass) -> ClassHash {
+    ClassHash(poseidon_hash_many(&[
+        *CONTRACT_CLASS_VERSION_FELT,
+        calculate_entry_points_hash(class, EntryPointType::External),
+        calculate_entry_points_hash(class, EntryPointType::L1Handler),
+        calculate_entry_points_hash(class, EntryPointType::Constructor),
+        starknet_keccak(class.abi.as_bytes()),
+        poseidon_hash_many(&class.sierra_program),
+    ]))
+}
+
+// The hash of the selectors and function indices of the entry points of the given type, in the
+// order in which they appear in the class.
+fn calculate_entry_points_hash(
+    class: &ContractClass,
+    entry_point_type: EntryPointType,
+) -> StarkHash {
+    let felts = class
+        .entry_point_by_type
+        .get(&entry_point_type)
+        .map(|entry_points| {
+            entry_points
+                .iter()
+                .flat_map(|entry_point| {
+                    [entry_point.selector.0, StarkFelt::from(entry_point.function_idx.0 as u64)]
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    poseidon_hash_many(&felts)
+}
+
+/// Calculates the hash of a deprecated (Cairo 0) contract class.
+pub fn calculate_deprecated_class_hash(
+    class: &DeprecatedContractClass,
+) -> Result<ClassHash, StarknetApiError> {
+    let builtins = json_array(&class.program.builtins, "builtins")?
+        .iter()
+        .map(|builtin| {
+            let builtin = builtin.as_str().ok_or_else(|| {
+                StarknetApiError::InvalidProgram(format!("Builtin {builtin} is not a string"))
+            })?;
+            FieldElement::from_byte_slice_be(builtin.as_bytes()).map(StarkFelt::from).map_err(
+                |_| StarknetApiError::InvalidProgram(format!("Builtin {builtin} is too long")),
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let data = json_array(&class.program.data, "data")?
+        .iter()
+        .map(|felt| {
+            felt.as_str().map(StarkFelt::try_from).unwrap_or_else(|| {
+                Err(StarknetApiError::InvalidProgram(format!("Data {felt} is not a string")))
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(ClassHash(pedersen_hash_array(&[
+        // The API version.
+        StarkFelt::from(0_u8),
+        calculate_deprecated_entry_points_hash(class, DeprecatedEntryPointType::External),
+        calculate_deprecated_entry_points_hash(class, DeprecatedEntryPointType::L1Handler),
+        calculate_deprecated_entry_points_hash(class, DeprecatedEntryPointType::Constructor),
+        pedersen_hash_array(&builtins),
+        calculate_deprecated_hinted_class_hash(class)?,
+        pedersen_hash_array(&data),
+    ])))
+}
+
+/// Calculates the hinted hash of a deprecated contract class: the [`starknet_keccak`] hash of its
+/// ABI and program without debug info, formatted as by Python's `json.dumps(..., sort_keys=True)`.
+/// Fields that older versions of the Cairo compiler did not output are removed when empty, so that
+/// the hash of old classes does not change.
+pub fn calculate_deprecated_hinted_class_hash(
+    class: &DeprecatedContractClass,
+) -> Result<StarkHash, StarknetApiError> {
+    let Value::Object(mut program) =
+        serde_json::to_value(&class.program).expect("A program should be serializable to JSON.")
+    else {
+        unreachable!("A program is serialized to a JSON object.");
+    };
+    program.insert("debug_info".to_owned(), Value::Null);
+
+    if matches!(program.get("compiler_version"), None | Some(Value::Null)) {
+        program.remove("compiler_version");
+        // Compilers before v0.10.0 formatted named tuples as "(a : felt)" instead of "(a: felt)".
+        for field in ["identifiers", "reference_manager"] {
+            if let Some(value) = program.get_mut(field) {
+                add_space_before_colons(value);
+            }
+        }
+    }
+
+    let has_attributes = match program.get_mut("attributes") {
+        Some(Value::Array(attributes)) if !attributes.is_empty() => {
+            for attribute in attributes.iter_mut().filter_map(Value::as_object_mut) {
+                if matches!(
+                    attribute.get("accessible_scopes"),
+                    Some(Value::Array(scopes)) if scopes.is_empty()
+                ) {
+                    attribute.remove("accessible_scopes");
+                }
+                if matches!(attribute.get("flow_tracking_data"), Some(Value::Null)) {
+                    attribute.remove("flow_tracking_data");
+                }
+            }
+            true
+        }
+        _ => false,
+    };
+    if !has_attributes {
+        program.remove("attributes");
+    }
+
+    // The ABI is part of the hash, so a class whose ABI is missing or could not be parsed cannot
+    // be hashed.
+    let abi = class.abi.as_ref().ok_or_else(|| {
+        StarknetApiError::InvalidContractClass("The ABI is missing or could not be parsed".into())
+    })?;
+    let abi = Value::Array(abi.iter().map(abi_entry_as_json).collect());
+    let mut hinted_class = Map::new();
+    hinted_class.insert("abi".to_owned(), abi);
+    hinted_class.insert("program".to_owned(), Value::Object(program));
+
+    let mut json = String::new();
+    write_python_json(&Value::Object(hinted_class), &mut json);
+    Ok(starknet_keccak(json.as_bytes()))
+}
+
+fn calculate_deprecated_entry_points_hash(
+    class: &DeprecatedContractClass,
+    entry_point_type: DeprecatedEntryPointType,
+) -> StarkHash {
+    let felts = class
+        .entry_points_by_type
+        .get(&entry_point_type)
+        .map(|entry_points| {
+            entry_points
+                .iter()
+                .flat_map(|entry_point| {
+                    [entry_point.selector.0, StarkFelt::from(entry_point.offset.0 as u64)]
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    pedersen_hash_array(&felts)
+}
+
+fn json_array<'a>(value: &'a Value, field: &str) -> Result<&'a Vec<Value>, StarknetApiError> {
+    value
+        .as_array()
+        .ok_or_else(|| StarknetApiError::InvalidProgram(format!("The {field} are not an array")))
+}
+
+// The ABI entry as it appears in the compiled class. The type of events and structs is not kept
+// when deserializing the entry.
+fn abi_entry_as_json(entry: &ContractClassAbiEntry) -> Value {
+    let mut json =
+        serde_json::to_value(entry).expect("An ABI entry should be serializable to JSON.");
+    let entry_type = match entry {
+        ContractClassAbiEntry::Event(_) => "event",
+        ContractClassAbiEntry::Struct(_) => "struct",
+        ContractClassAbiEntry::Function(_) => return json,
+    };
+    if let Value::Object(fields) = &mut json {
+        fields.insert("type".to_owned(), Value::String(entry_type.to_owned()));
+    }
+    json
+}
+
+// Replaces ": " with " : " in the "cairo_type" and "value" strings of the JSON value.
+fn add_space_before_colons(value: &mut Value) {
+    match value {
+        Value::Array(values) => values.iter_mut().for_each(add_space_before_colons),
+        Value::Object(fields) => {
+            for (key, value) in fields.iter_mut() {
+                match value {
+                    Value::String(string) if key == "cairo_type" || key == "value" => {
+                        // A string that is already formatted as " : " is not changed.
+                        *string = string.replace(": ", " : ").replace("  :", " :");
+                    }
+                    _ => add_space_before_colons(value),
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+// Writes the JSON value as Python's `json.dumps(value, sort_keys=True)`: keys are sorted, items are
+// separated by ", " and ": ", and characters outside of printable ASCII are escaped.
+fn write_python_json(value: &Value, out: &mut String) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(boolean) => out.push_str(if *boolean { "true" } else { "false" }),
+        Value::Number(number) => out.push_str(&number.to_string()),
+        Value::String(string) => write_python_json_string(string, out),
+        Value::Array(values) => {
+            out.push('[');
+            for (index, value) in values.iter().enumerate() {
+                if index > 0 {
+                    out.push_str(", ");
+                }
+                write_python_json(value, out);
+            }
+            out.push(']');
+        }
+        Value::Object(fields) => {
+            let mut fields = fields.iter().collect::<Vec<_>>();
+            // The hints are keyed by program counters, which are integers in Python and are sorted
+            // as such.
+            fields.sort_by_key(|(key, _)| (key.parse::<u64>().ok(), *key));
+            out.push('{');
+            for (index, (key, value)) in fields.into_iter().enumerate() {
+                if index > 0 {
+                    out.push_str(", ");
+                }
+                write_python_json_string(key, out);
+                out.push_str(": ");
+                write_python_json(value, out);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn write_python_json_string(string: &str, out: &mut String) {
+    out.push('"');
+    for character in string.chars() {
+        match character {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            ' '..='~' => out.push(character),
+            _ => {
+                let mut buffer = [0_u16; 2];
+                for unit in character.encode_utf16(&mut buffer) {
+                    out.push_str(&format!("\\u{unit:04x}"));
+                }
+            }
+        }
+    }
+    out.push('"');
+}
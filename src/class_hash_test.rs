@@ -0,0 +1,403 @@
+use std::collections::HashMap;
+
+use assert_matches::assert_matches;
+use serde_json::json;
+use starknet_crypto::FieldElement;
+
+use crate::class_hash::{
+    calculate_class_hash, calculate_deprecated_class_hash, calculate_deprecated_hinted_class_hash,
+};
+use crate::core::{ClassHash, EntryPointSelector};
+use crate::deprecated_contract_class::ContractClass as DeprecatedContractClass;
+use crate::hash::{pedersen_hash_array, poseidon_hash_many, starknet_keccak, StarkFelt};
+use crate::state::{ContractClass, EntryPoint, EntryPointType, FunctionIndex};
+use crate::test_utils::read_json_file;
+use crate::{stark_felt, StarknetApiError};
+
+const DEPRECATED_CLASS: &str = r#"
+{
+  "abi": [
+    {
+      "type": "struct",
+      "name": "Point",
+      "size": 2,
+      "members": [
+        {
+          "name": "x",
+          "type": "felt",
+          "offset": 0
+        },
+        {
+          "name": "y",
+          "type": "felt",
+          "offset": 1
+        }
+      ]
+    },
+    {
+      "type": "event",
+      "name": "moved",
+      "keys": [],
+      "data": [
+        {
+          "name": "to",
+          "type": "Point"
+        }
+      ]
+    },
+    {
+      "type": "function",
+      "name": "get_balance",
+      "inputs": [],
+      "outputs": [
+        {
+          "name": "res",
+          "type": "felt"
+        }
+      ],
+      "stateMutability": "view"
+    },
+    {
+      "type": "constructor",
+      "name": "constructor",
+      "inputs": [
+        {
+          "name": "owner",
+          "type": "felt"
+        }
+      ],
+      "outputs": []
+    }
+  ],
+  "entry_points_by_type": {
+    "CONSTRUCTOR": [
+      {
+        "selector": "0x28ffe4ff0f226a9107253e17a904099aa4f63a02a5621de0576e5aa71bc5194",
+        "offset": "0x5"
+      }
+    ],
+    "EXTERNAL": [
+      {
+        "selector": "0x39e11d48192e4333233c7eb19d10ad67c362bb28580c604d67884c85da39695",
+        "offset": "0x0"
+      }
+    ],
+    "L1_HANDLER": []
+  },
+  "program": {
+    "attributes": [
+      {
+        "name": "error_message",
+        "value": "Café: balance too low",
+        "start_pc": 1,
+        "end_pc": 3,
+        "accessible_scopes": [],
+        "flow_tracking_data": null
+      },
+      {
+        "name": "error_message",
+        "value": "other",
+        "start_pc": 4,
+        "end_pc": 5,
+        "accessible_scopes": [
+          "__main__"
+        ],
+        "flow_tracking_data": {
+          "ap_tracking": {
+            "group": 1,
+            "offset": 0
+          },
+          "reference_ids": {}
+        }
+      }
+    ],
+    "builtins": [
+      "pedersen",
+      "range_check"
+    ],
+    "data": [
+      "0x480680017fff8000",
+      "0x1",
+      "0x208b7fff7fff7ffe"
+    ],
+    "debug_info": {
+      "file_contents": {},
+      "instruction_locations": {
+        "0": {}
+      }
+    },
+    "hints": {
+      "0": [
+        {
+          "code": "memory[ap] = to_felt_or_relocatable(ids.x)\n",
+          "accessible_scopes": [
+            "__main__"
+          ],
+          "flow_tracking_data": {
+            "ap_tracking": {
+              "group": 0,
+              "offset": 0
+            },
+            "reference_ids": {
+              "__main__.x": 0
+            }
+          }
+        }
+      ]
+    },
+    "identifiers": {
+      "__main__.Point": {
+        "type": "struct",
+        "full_name": "__main__.Point",
+        "size": 2,
+        "members": {
+          "x": {
+            "cairo_type": "felt",
+            "offset": 0
+          }
+        }
+      },
+      "__main__.Tuple": {
+        "type": "type_definition",
+        "cairo_type": "(x: felt, y : felt)"
+      }
+    },
+    "main_scope": "__main__",
+    "prime": "0x800000000000011000000000000000000000000000000000000000000000001",
+    "reference_manager": {
+      "references": [
+        {
+          "ap_tracking_data": {
+            "group": 0,
+            "offset": 0
+          },
+          "pc": 0,
+          "value": "[cast(fp + (-3), (x: felt)*)]"
+        }
+      ]
+    }
+  }
+}
+"#;
+
+// The hinted class as formatted by Python's json.dumps, for a program without a compiler version.
+const HINTED_CLASS_WITHOUT_COMPILER_VERSION: &str = concat!(
+    r#"{"abi": [{"members": [{"name": "x", "offset": 0, "type": "felt"}, {"name": "y", "#,
+    r#""offset": 1, "type": "felt"}], "name": "Point", "size": 2, "type": "struct"}, "#,
+    r#"{"data": [{"name": "to", "type": "Point"}], "keys": [], "name": "moved", "#,
+    r#""type": "event"}, {"inputs": [], "name": "get_balance", "outputs": [{"name": "res", "#,
+    r#""type": "felt"}], "stateMutability": "view", "type": "function"}, "#,
+    r#"{"inputs": [{"name": "owner", "type": "felt"}], "name": "constructor", "outputs": [], "#,
+    r#""type": "constructor"}], "program": {"attributes": [{"end_pc": 3, "#,
+    r#""name": "error_message", "start_pc": 1, "value": "Caf\u00e9: balance too low"}, "#,
+    r#"{"accessible_scopes": ["__main__"], "end_pc": 5, "#,
+    r#""flow_tracking_data": {"ap_tracking": {"group": 1, "offset": 0}, "reference_ids": {}}, "#,
+    r#""name": "error_message", "start_pc": 4, "value": "other"}], "builtins": ["pedersen", "#,
+    r#""range_check"], "data": ["0x480680017fff8000", "0x1", "0x208b7fff7fff7ffe"], "#,
+    r#""debug_info": null, "hints": {"0": [{"accessible_scopes": ["__main__"], "#,
+    r#""code": "memory[ap] = to_felt_or_relocatable(ids.x)\n", "#,
+    r#""flow_tracking_data": {"ap_tracking": {"group": 0, "offset": 0}, "#,
+    r#""reference_ids": {"__main__.x": 0}}}]}, "#,
+    r#""identifiers": {"__main__.Point": {"full_name": "__main__.Point", "#,
+    r#""members": {"x": {"cairo_type": "felt", "offset": 0}}, "size": 2, "type": "struct"}, "#,
+    r#""__main__.Tuple": {"cairo_type": "(x : felt, y : felt)", "type": "type_definition"}}, "#,
+    r#""main_scope": "__main__", "#,
+    r#""prime": "0x800000000000011000000000000000000000000000000000000000000000001", "#,
+    r#""reference_manager": {"references": [{"ap_tracking_data": {"group": 0, "offset": 0}, "#,
+    r#""pc": 0, "value": "[cast(fp + (-3), (x : felt)*)]"}]}}}"#,
+);
+
+// The hinted class as formatted by Python's json.dumps, for a program with a compiler version.
+const HINTED_CLASS_WITH_COMPILER_VERSION: &str = concat!(
+    r#"{"abi": [{"members": [{"name": "x", "offset": 0, "type": "felt"}, {"name": "y", "#,
+    r#""offset": 1, "type": "felt"}], "name": "Point", "size": 2, "type": "struct"}, "#,
+    r#"{"data": [{"name": "to", "type": "Point"}], "keys": [], "name": "moved", "#,
+    r#""type": "event"}, {"inputs": [], "name": "get_balance", "outputs": [{"name": "res", "#,
+    r#""type": "felt"}], "stateMutability": "view", "type": "function"}, "#,
+    r#"{"inputs": [{"name": "owner", "type": "felt"}], "name": "constructor", "outputs": [], "#,
+    r#""type": "constructor"}], "program": {"attributes": [{"end_pc": 3, "#,
+    r#""name": "error_message", "start_pc": 1, "value": "Caf\u00e9: balance too low"}, "#,
+    r#"{"accessible_scopes": ["__main__"], "end_pc": 5, "#,
+    r#""flow_tracking_data": {"ap_tracking": {"group": 1, "offset": 0}, "reference_ids": {}}, "#,
+    r#""name": "error_message", "start_pc": 4, "value": "other"}], "builtins": ["pedersen", "#,
+    r#""range_check"], "compiler_version": "0.10.3", "data": ["0x480680017fff8000", "0x1", "#,
+    r#""0x208b7fff7fff7ffe"], "debug_info": null, "#,
+    r#""hints": {"0": [{"accessible_scopes": ["__main__"], "#,
+    r#""code": "memory[ap] = to_felt_or_relocatable(ids.x)\n", "#,
+    r#""flow_tracking_data": {"ap_tracking": {"group": 0, "offset": 0}, "#,
+    r#""reference_ids": {"__main__.x": 0}}}]}, "#,
+    r#""identifiers": {"__main__.Point": {"full_name": "__main__.Point", "#,
+    r#""members": {"x": {"cairo_type": "felt", "offset": 0}}, "size": 2, "type": "struct"}, "#,
+    r#""__main__.Tuple": {"cairo_type": "(x: felt, y : felt)", "type": "type_definition"}}, "#,
+    r#""main_scope": "__main__", "#,
+    r#""prime": "0x800000000000011000000000000000000000000000000000000000000000001", "#,
+    r#""reference_manager": {"references": [{"ap_tracking_data": {"group": 0, "offset": 0}, "#,
+    r#""pc": 0, "value": "[cast(fp + (-3), (x: felt)*)]"}]}}}"#,
+);
+
+fn deprecated_class() -> DeprecatedContractClass {
+    serde_json::from_str(DEPRECATED_CLASS).unwrap()
+}
+
+#[test]
+fn class_hash() {
+    let class = ContractClass {
+        sierra_program: vec![stark_felt!("0x1"), stark_felt!("0x2"), stark_felt!("0x3")],
+        entry_point_by_type: HashMap::from([
+            (
+                EntryPointType::External,
+                vec![
+                    EntryPoint {
+                        function_idx: FunctionIndex(1),
+                        selector: EntryPointSelector::from_name("increase_balance"),
+                    },
+                    EntryPoint {
+                        function_idx: FunctionIndex(0),
+                        selector: EntryPointSelector::from_name("get_balance"),
+                    },
+                ],
+            ),
+            (
+                EntryPointType::Constructor,
+                vec![EntryPoint {
+                    function_idx: FunctionIndex(2),
+                    selector: EntryPointSelector::from_name("constructor"),
+                }],
+            ),
+        ]),
+        abi: "[]".to_owned(),
+    };
+
+    let expected = poseidon_hash_many(&[
+        FieldElement::from_byte_slice_be(b"CONTRACT_CLASS_V0.1.0").unwrap().into(),
+        poseidon_hash_many(&[
+            EntryPointSelector::from_name("increase_balance").0,
+            stark_felt!("0x1"),
+            EntryPointSelector::from_name("get_balance").0,
+            stark_felt!("0x0"),
+        ]),
+        poseidon_hash_many(&[]),
+        poseidon_hash_many(&[EntryPointSelector::from_name("constructor").0, stark_felt!("0x2")]),
+        starknet_keccak(b"[]"),
+        poseidon_hash_many(&class.sierra_program),
+    ]);
+    assert_eq!(calculate_class_hash(&class), ClassHash(expected));
+
+    let mut other_abi = class.clone();
+    other_abi.abi = "[ ]".to_owned();
+    assert_ne!(calculate_class_hash(&other_abi), ClassHash(expected));
+}
+
+#[test]
+fn mainnet_class_hash() {
+    // The Argent account v0.3.0 class, as declared on mainnet.
+    let class: ContractClass =
+        serde_json::from_value(read_json_file("contract_class.json")).unwrap();
+    assert_eq!(
+        calculate_class_hash(&class),
+        ClassHash(stark_felt!("0x29927c8af6bccf3f6fda035981e765a7bdbf18a2dc0d630494f8758aa908e2b"))
+    );
+}
+
+#[test]
+fn empty_class_hash() {
+    let empty_hash = poseidon_hash_many(&[]);
+    let expected = poseidon_hash_many(&[
+        StarkFelt::from(FieldElement::from_byte_slice_be(b"CONTRACT_CLASS_V0.1.0").unwrap()),
+        empty_hash,
+        empty_hash,
+        empty_hash,
+        starknet_keccak(b""),
+        empty_hash,
+    ]);
+    assert_eq!(calculate_class_hash(&ContractClass::default()), ClassHash(expected));
+}
+
+#[test]
+fn hinted_class_hash() {
+    let class = deprecated_class();
+    assert_eq!(
+        calculate_deprecated_hinted_class_hash(&class).unwrap(),
+        starknet_keccak(HINTED_CLASS_WITHOUT_COMPILER_VERSION.as_bytes())
+    );
+
+    let mut class = deprecated_class();
+    class.program.compiler_version = json!("0.10.3");
+    assert_eq!(
+        calculate_deprecated_hinted_class_hash(&class).unwrap(),
+        starknet_keccak(HINTED_CLASS_WITH_COMPILER_VERSION.as_bytes())
+    );
+}
+
+#[test]
+fn hinted_class_hash_without_attributes() {
+    let mut class = deprecated_class();
+    class.program.attributes = json!([]);
+    let without_attributes = calculate_deprecated_hinted_class_hash(&class).unwrap();
+    // A program from before attributes were added.
+    class.program.attributes = serde_json::Value::Null;
+    assert_eq!(calculate_deprecated_hinted_class_hash(&class).unwrap(), without_attributes);
+    // The debug info is not part of the hash.
+    class.program.debug_info = json!({});
+    assert_eq!(calculate_deprecated_hinted_class_hash(&class).unwrap(), without_attributes);
+}
+
+#[test]
+fn deprecated_class_hash() {
+    let class = deprecated_class();
+    let expected = pedersen_hash_array(&[
+        stark_felt!("0x0"),
+        pedersen_hash_array(&[
+            stark_felt!("0x39e11d48192e4333233c7eb19d10ad67c362bb28580c604d67884c85da39695"),
+            stark_felt!("0x0"),
+        ]),
+        pedersen_hash_array(&[]),
+        pedersen_hash_array(&[
+            stark_felt!("0x28ffe4ff0f226a9107253e17a904099aa4f63a02a5621de0576e5aa71bc5194"),
+            stark_felt!("0x5"),
+        ]),
+        pedersen_hash_array(&[
+            StarkFelt::try_from(format!("0x{}", hex::encode("pedersen")).as_str()).unwrap(),
+            StarkFelt::try_from(format!("0x{}", hex::encode("range_check")).as_str()).unwrap(),
+        ]),
+        starknet_keccak(HINTED_CLASS_WITHOUT_COMPILER_VERSION.as_bytes()),
+        pedersen_hash_array(&[
+            stark_felt!("0x480680017fff8000"),
+            stark_felt!("0x1"),
+            stark_felt!("0x208b7fff7fff7ffe"),
+        ]),
+    ]);
+    assert_eq!(calculate_deprecated_class_hash(&class).unwrap(), ClassHash(expected));
+}
+
+#[test]
+fn compiled_deprecated_class_hash() {
+    // A class compiled with Cairo v0.11.1.1. Its program has constants that do not fit in 64 bits
+    // and more than ten hints, whose keys are sorted as integers.
+    let class: DeprecatedContractClass =
+        serde_json::from_value(read_json_file("deprecated_contract_class.json")).unwrap();
+    assert_eq!(
+        calculate_deprecated_class_hash(&class).unwrap(),
+        ClassHash(stark_felt!("0x52227bef83e5a6f807820707eb099ea5456333aa1c535b068f9e9fbbf90c27f"))
+    );
+}
+
+#[test]
+fn deprecated_class_hash_invalid_program() {
+    let mut class = deprecated_class();
+    class.program.data = json!(["0x1", 2]);
+    assert_matches!(
+        calculate_deprecated_class_hash(&class),
+        Err(StarknetApiError::InvalidProgram(_))
+    );
+}
+
+#[test]
+fn deprecated_class_hash_without_abi() {
+    let mut class = deprecated_class();
+    class.abi = None;
+    assert_matches!(
+        calculate_deprecated_class_hash(&class),
+        Err(StarknetApiError::InvalidContractClass(_))
+    );
+}
@@ -0,0 +1,148 @@
+//! The compiled (CASM) form of a Sierra [ContractClass](`crate::state::ContractClass`).
+#[cfg(test)]
+#[path = "compiled_class_test.rs"]
+mod compiled_class_test;
+
+use std::collections::HashMap;
+
+#[cfg(feature = "sierra_compiler")]
+use cairo_lang_starknet::casm_contract_class::CasmContractClass;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use starknet_crypto::FieldElement;
+
+use crate::core::{CompiledClassHash, EntryPointSelector};
+use crate::hash::{poseidon_hash_many, StarkFelt, StarkHash};
+use crate::state::EntryPointType;
+use crate::StarknetApiError;
+
+/// The version of the compiled contract classes, which is part of their hash.
+pub const COMPILED_CLASS_VERSION: &str = "COMPILED_CLASS_V1";
+
+static COMPILED_CLASS_VERSION_FELT: Lazy<StarkFelt> = Lazy::new(|| {
+    StarkFelt::from(
+        FieldElement::from_byte_slice_be(COMPILED_CLASS_VERSION.as_bytes())
+            .expect("The compiled class version should fit in a FieldElement."),
+    )
+});
+
+/// A compiled contract class.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct CompiledContractClass {
+    pub prime: serde_json::Value,
+    pub compiler_version: String,
+    pub bytecode: Vec<StarkFelt>,
+    /// The lengths of the segments of the bytecode, if the bytecode is segmented.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bytecode_segment_lengths: Option<NestedIntList>,
+    pub hints: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pythonic_hints: Option<serde_json::Value>,
+    pub entry_points_by_type: HashMap<EntryPointType, Vec<CompiledEntryPoint>>,
+}
+
+/// An entry point of a [CompiledContractClass](`crate::compiled_class::CompiledContractClass`).
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+pub struct CompiledEntryPoint {
+    pub selector: EntryPointSelector,
+    pub offset: usize,
+    pub builtins: Vec<String>,
+}
+
+/// A tree of lengths, describing how the bytecode of a compiled class is split into segments.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum NestedIntList {
+    /// A segment of the given length.
+    Leaf(usize),
+    /// A segment that is split into the given segments.
+    Node(Vec<NestedIntList>),
+}
+
+impl CompiledContractClass {
+    /// Returns the hash of the compiled class.
+    pub fn compiled_class_hash(&self) -> Result<CompiledClassHash, StarknetApiError> {
+        Ok(CompiledClassHash(poseidon_hash_many(&[
+            *COMPILED_CLASS_VERSION_FELT,
+            self.entry_points_hash(EntryPointType::External)?,
+            self.entry_points_hash(EntryPointType::L1Handler)?,
+            self.entry_points_hash(EntryPointType::Constructor)?,
+            self.bytecode_hash()?,
+        ])))
+    }
+
+    // The hash of the selector, offset and builtins of the entry points of the given type.
+    fn entry_points_hash(
+        &self,
+        entry_point_type: EntryPointType,
+    ) -> Result<StarkHash, StarknetApiError> {
+        let mut felts = Vec::new();
+        for entry_point in self.entry_points_by_type.get(&entry_point_type).into_iter().flatten() {
+            let builtins = entry_point
+                .builtins
+                .iter()
+                .map(|builtin| {
+                    FieldElement::from_byte_slice_be(builtin.as_bytes())
+                        .map(StarkFelt::from)
+                        .map_err(|_| StarknetApiError::OutOfRange { string: builtin.clone() })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            felts.push(entry_point.selector.0);
+            felts.push(StarkFelt::from(entry_point.offset as u64));
+            felts.push(poseidon_hash_many(&builtins));
+        }
+        Ok(poseidon_hash_many(&felts))
+    }
+
+    fn bytecode_hash(&self) -> Result<StarkHash, StarknetApiError> {
+        let Some(segment_lengths) = &self.bytecode_segment_lengths else {
+            return Ok(poseidon_hash_many(&self.bytecode));
+        };
+        let invalid_lengths = || StarknetApiError::InvalidBytecodeSegmentLengths {
+            bytecode_length: self.bytecode.len(),
+        };
+        let (length, hash) =
+            bytecode_segment_hash(&self.bytecode, segment_lengths).ok_or_else(invalid_lengths)?;
+        if length != self.bytecode.len() {
+            return Err(invalid_lengths());
+        }
+        Ok(hash)
+    }
+}
+
+#[cfg(feature = "sierra_compiler")]
+impl TryFrom<CasmContractClass> for CompiledContractClass {
+    type Error = StarknetApiError;
+
+    fn try_from(casm_class: CasmContractClass) -> Result<Self, Self::Error> {
+        let invalid_class =
+            |err: serde_json::Error| StarknetApiError::InvalidCompiledClass(err.to_string());
+        let json = serde_json::to_value(casm_class).map_err(invalid_class)?;
+        serde_json::from_value(json).map_err(invalid_class)
+    }
+}
+
+// Returns the length and hash of the segment at the start of the bytecode. The hash of a leaf is
+// the hash of its bytecode, and the hash of a node is `1 + H(length0, hash0, length1, ...)`.
+fn bytecode_segment_hash(
+    bytecode: &[StarkFelt],
+    segment_lengths: &NestedIntList,
+) -> Option<(usize, StarkHash)> {
+    match segment_lengths {
+        NestedIntList::Leaf(length) => {
+            Some((*length, poseidon_hash_many(bytecode.get(..*length)?)))
+        }
+        NestedIntList::Node(segments) => {
+            let mut offset = 0;
+            let mut felts = Vec::with_capacity(2 * segments.len());
+            for segment in segments {
+                let (length, hash) = bytecode_segment_hash(bytecode.get(offset..)?, segment)?;
+                felts.push(StarkFelt::from(length as u64));
+                felts.push(hash);
+                offset += length;
+            }
+            let hash = FieldElement::from(poseidon_hash_many(&felts)) + FieldElement::ONE;
+            Some((offset, StarkFelt::from(hash)))
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use assert_matches::assert_matches;
+#[cfg(feature = "sierra_compiler")]
+use cairo_lang_starknet::casm_contract_class::CasmContractClass;
+
+use crate::compiled_class::{CompiledContractClass, NestedIntList};
+use crate::core::CompiledClassHash;
+use crate::hash::StarkFelt;
+use crate::{stark_felt, StarknetApiError};
+
+const COMPILED_CLASS: &str = r#"
+{
+  "prime": "0x800000000000011000000000000000000000000000000000000000000000001",
+  "compiler_version": "2.6.4",
+  "bytecode": [
+    "0xa0680017fff8000",
+    "0x7",
+    "0x482680017ffa8000",
+    "0x100000000000000000000000000000000",
+    "0x400280007ff97fff",
+    "0x10780017fff7fff",
+    "0x8f",
+    "0x4825800180007ffa"
+  ],
+  "hints": [],
+  "entry_points_by_type": {
+    "EXTERNAL": [
+      {
+        "selector": "0x362398bec32bc0ebb411203221a35a0301193a96f317ebe5e40be9f60d15320",
+        "offset": 0,
+        "builtins": ["range_check"]
+      },
+      {
+        "selector": "0x39e11d48192e4333233c7eb19d10ad67c362bb28580c604d67884c85da39695",
+        "offset": 5,
+        "builtins": ["pedersen", "range_check"]
+      }
+    ],
+    "L1_HANDLER": [],
+    "CONSTRUCTOR": [
+      {
+        "selector": "0x28ffe4ff0f226a9107253e17a904099aa4f63a02a5621de0576e5aa71bc5194",
+        "offset": 3,
+        "builtins": []
+      }
+    ]
+  }
+}
+"#;
+
+fn compiled_class() -> CompiledContractClass {
+    serde_json::from_str(COMPILED_CLASS).unwrap()
+}
+
+// The expected hashes were calculated with `CasmContractClass::compiled_class_hash` of
+// cairo-lang-starknet-classes v2.6.4, as the cairo-lang-starknet version that this crate depends
+// on does not support segmented bytecode.
+#[test]
+fn compiled_class_hash() {
+    assert_eq!(
+        compiled_class().compiled_class_hash().unwrap(),
+        CompiledClassHash(stark_felt!(
+            "0x1e23360c31ef9dc47b9242285eee30f4b7bd4e61ebe0b4643a1f2b7110286ec"
+        ))
+    );
+}
+
+#[test]
+fn segmented_compiled_class_hash() {
+    let mut class = compiled_class();
+    class.bytecode_segment_lengths = Some(NestedIntList::Node(vec![
+        NestedIntList::Leaf(2),
+        NestedIntList::Node(vec![NestedIntList::Leaf(3), NestedIntList::Leaf(1)]),
+        NestedIntList::Leaf(2),
+    ]));
+    assert_eq!(
+        class.compiled_class_hash().unwrap(),
+        CompiledClassHash(stark_felt!(
+            "0x622de5a0972bfa783c8403dfa63d16a9fa54956d5d5b75f77360847308b015a"
+        ))
+    );
+
+    // A single segment is not the same as no segmentation.
+    class.bytecode_segment_lengths = Some(NestedIntList::Node(vec![NestedIntList::Leaf(8)]));
+    assert_ne!(
+        class.compiled_class_hash().unwrap(),
+        compiled_class().compiled_class_hash().unwrap()
+    );
+}
+
+#[test]
+fn invalid_bytecode_segment_lengths() {
+    let mut class = compiled_class();
+    for lengths in [
+        NestedIntList::Leaf(7),
+        NestedIntList::Node(vec![NestedIntList::Leaf(4), NestedIntList::Leaf(5)]),
+    ] {
+        class.bytecode_segment_lengths = Some(lengths);
+        assert_matches!(
+            class.compiled_class_hash(),
+            Err(StarknetApiError::InvalidBytecodeSegmentLengths { bytecode_length: 8 })
+        );
+    }
+}
+
+#[test]
+fn compiled_class_serde() {
+    let mut class = compiled_class();
+    class.bytecode_segment_lengths =
+        Some(NestedIntList::Node(vec![NestedIntList::Leaf(2), NestedIntList::Leaf(6)]));
+    let json = serde_json::to_value(&class).unwrap();
+    assert_eq!(json["bytecode_segment_lengths"], serde_json::json!([2, 6]));
+    assert_eq!(serde_json::from_value::<CompiledContractClass>(json).unwrap(), class);
+}
+
+#[cfg(feature = "sierra_compiler")]
+#[test]
+fn from_casm_contract_class() {
+    let casm_class: CasmContractClass = serde_json::from_str(COMPILED_CLASS).unwrap();
+    assert_eq!(CompiledContractClass::try_from(casm_class).unwrap(), compiled_class());
+}
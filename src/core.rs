@@ -9,7 +9,7 @@ use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use starknet_crypto::FieldElement;
 
-use crate::hash::{pedersen_hash_array, StarkFelt, StarkHash};
+use crate::hash::{starknet_keccak, HashFunction, StarkFelt, StarkHash};
 use crate::transaction::{Calldata, ContractAddressSalt};
 use crate::StarknetApiError;
 
@@ -52,16 +52,16 @@ impl TryFrom<StarkHash> for ContractAddress {
     }
 }
 
-// TODO: Add a hash_function as a parameter
-pub fn calculate_contract_address(
+/// Calculates the address of a contract deployed by `deployer_address`, hashing with `H`.
+pub fn calculate_contract_address<H: HashFunction>(
     salt: ContractAddressSalt,
     class_hash: ClassHash,
     constructor_calldata: &Calldata,
     deployer_address: ContractAddress,
 ) -> Result<ContractAddress, StarknetApiError> {
-    let constructor_calldata_hash = pedersen_hash_array(&constructor_calldata.0);
+    let constructor_calldata_hash = H::hash_array(&constructor_calldata.0);
     let contract_address_prefix = format!("0x{}", hex::encode(CONTRACT_ADDRESS_PREFIX));
-    let mut address = FieldElement::from(pedersen_hash_array(&[
+    let mut address = FieldElement::from(H::hash_array(&[
         StarkFelt::try_from(contract_address_prefix.as_str())?,
         *deployer_address.0.key(),
         salt.0,
@@ -119,6 +119,24 @@ pub struct Nonce(pub StarkFelt);
 )]
 pub struct EntryPointSelector(pub StarkHash);
 
+/// The name of the entry point that is called when a contract has no entry point with the called
+/// selector.
+pub const DEFAULT_ENTRY_POINT_NAME: &str = "__default__";
+/// The name of the L1 handler that is called when a contract has no L1 handler with the called
+/// selector.
+pub const DEFAULT_L1_ENTRY_POINT_NAME: &str = "__l1_default__";
+
+impl EntryPointSelector {
+    /// Returns the selector of the entry point with the given name: the [`starknet_keccak`] hash
+    /// of the name, or 0 for the default entry points.
+    pub fn from_name(name: &str) -> Self {
+        if name == DEFAULT_ENTRY_POINT_NAME || name == DEFAULT_L1_ENTRY_POINT_NAME {
+            return Self(StarkHash::default());
+        }
+        Self(starknet_keccak(name.as_bytes()))
+    }
+}
+
 /// The root of the global state at a [Block](`crate::block::Block`)
 /// and [StateUpdate](`crate::state::StateUpdate`).
 #[derive(
@@ -2,10 +2,10 @@ use assert_matches::assert_matches;
 use starknet_crypto::FieldElement;
 
 use crate::core::{
-    calculate_contract_address, ClassHash, ContractAddress, PatriciaKey, StarknetApiError,
-    CONTRACT_ADDRESS_PREFIX, L2_ADDRESS_UPPER_BOUND,
+    calculate_contract_address, ClassHash, ContractAddress, EntryPointSelector, PatriciaKey,
+    StarknetApiError, CONTRACT_ADDRESS_PREFIX, L2_ADDRESS_UPPER_BOUND,
 };
-use crate::hash::{pedersen_hash_array, StarkFelt, StarkHash};
+use crate::hash::{pedersen_hash_array, Pedersen, StarkFelt, StarkHash};
 use crate::transaction::{Calldata, ContractAddressSalt};
 use crate::{patricia_key, stark_felt};
 
@@ -47,9 +47,13 @@ fn test_calculate_contract_address() {
     let constructor_calldata =
         Calldata(vec![stark_felt!(60_u16), stark_felt!(70_u16), FieldElement::MAX.into()].into());
 
-    let actual_address =
-        calculate_contract_address(salt, class_hash, &constructor_calldata, deployer_address)
-            .unwrap();
+    let actual_address = calculate_contract_address::<Pedersen>(
+        salt,
+        class_hash,
+        &constructor_calldata,
+        deployer_address,
+    )
+    .unwrap();
 
     let constructor_calldata_hash = pedersen_hash_array(&constructor_calldata.0);
     let address = pedersen_hash_array(&[
@@ -65,3 +69,15 @@ fn test_calculate_contract_address() {
 
     assert_eq!(actual_address, expected_address);
 }
+
+#[test]
+fn entry_point_selector_from_name() {
+    assert_eq!(
+        EntryPointSelector::from_name("transfer"),
+        EntryPointSelector(stark_felt!(
+            "0x83afd3f4caedc6eebf44246fe54e38c95e3179a5ec9ea81740eca5b482d12e"
+        ))
+    );
+    assert_eq!(EntryPointSelector::from_name("__default__"), EntryPointSelector::default());
+    assert_eq!(EntryPointSelector::from_name("__l1_default__"), EntryPointSelector::default());
+}
@@ -0,0 +1,95 @@
+//! ECDSA signatures of transaction hashes over the
+//! [STARK curve](https://docs.starknet.io/documentation/architecture_and_concepts/Cryptography/stark-curve/).
+#[cfg(test)]
+#[path = "crypto_test.rs"]
+mod crypto_test;
+
+use serde::{Deserialize, Serialize};
+use starknet_crypto::{get_public_key, rfc6979_generate_k, sign, verify, FieldElement, SignError};
+
+use crate::hash::StarkFelt;
+use crate::transaction::{TransactionHash, TransactionSignature};
+use crate::StarknetApiError;
+
+/// A public key: the x coordinate of a point on the STARK curve.
+#[derive(
+    Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord,
+)]
+pub struct PublicKey(pub StarkFelt);
+
+/// Signs transaction hashes. The signature of a hash is `[r, s]`.
+pub trait TransactionSigner {
+    /// Returns the public key that verifies the signatures of the signer.
+    fn public_key(&self) -> PublicKey;
+
+    /// Signs a transaction hash.
+    fn sign(&self, hash: &TransactionHash) -> Result<TransactionSignature, StarknetApiError>;
+}
+
+/// A [`TransactionSigner`] that holds its private key in memory, and signs with a deterministic
+/// nonce, as defined in [RFC 6979](https://www.rfc-editor.org/rfc/rfc6979).
+#[derive(Clone)]
+pub struct PrivateKeySigner {
+    private_key: FieldElement,
+}
+
+impl PrivateKeySigner {
+    pub fn new(private_key: StarkFelt) -> Self {
+        Self { private_key: FieldElement::from(private_key) }
+    }
+}
+
+impl std::fmt::Debug for PrivateKeySigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PrivateKeySigner").field("public_key", &self.public_key()).finish()
+    }
+}
+
+impl TransactionSigner for PrivateKeySigner {
+    fn public_key(&self) -> PublicKey {
+        PublicKey(StarkFelt::from(get_public_key(&self.private_key)))
+    }
+
+    fn sign(&self, hash: &TransactionHash) -> Result<TransactionSignature, StarknetApiError> {
+        let message = FieldElement::from(hash.0);
+        // A nonce that gives an invalid signature is replaced by the nonce of the next seed.
+        let mut seed = None;
+        loop {
+            let k = rfc6979_generate_k(&message, &self.private_key, seed.as_ref());
+            match sign(&self.private_key, &message, &k) {
+                Ok(signature) => {
+                    return Ok(TransactionSignature(vec![
+                        StarkFelt::from(signature.r),
+                        StarkFelt::from(signature.s),
+                    ]));
+                }
+                Err(SignError::InvalidK) => {
+                    seed = Some(seed.unwrap_or(FieldElement::ZERO) + FieldElement::ONE);
+                }
+                Err(err) => return Err(StarknetApiError::SigningFailed(err.to_string())),
+            }
+        }
+    }
+}
+
+/// Returns true if `signature` is a valid `[r, s]` signature of the transaction hash by the owner
+/// of the public key. Returns an error if the signature or the public key are malformed.
+pub fn verify_transaction_signature(
+    public_key: &PublicKey,
+    hash: &TransactionHash,
+    signature: &TransactionSignature,
+) -> Result<bool, StarknetApiError> {
+    let [r, s] = signature.0[..] else {
+        return Err(StarknetApiError::InvalidSignature(format!(
+            "expected 2 elements, found {}",
+            signature.0.len()
+        )));
+    };
+    verify(
+        &FieldElement::from(public_key.0),
+        &FieldElement::from(hash.0),
+        &FieldElement::from(r),
+        &FieldElement::from(s),
+    )
+    .map_err(|err| StarknetApiError::InvalidSignature(err.to_string()))
+}
@@ -0,0 +1,50 @@
+use assert_matches::assert_matches;
+
+use crate::crypto::{verify_transaction_signature, PrivateKeySigner, PublicKey, TransactionSigner};
+use crate::hash::StarkFelt;
+use crate::transaction::{TransactionHash, TransactionSignature};
+use crate::{stark_felt, StarknetApiError};
+
+#[test]
+fn public_key() {
+    // The public key of the private key 1 is the generator of the curve.
+    let signer = PrivateKeySigner::new(stark_felt!("0x1"));
+    assert_eq!(
+        signer.public_key(),
+        PublicKey(stark_felt!("0x1ef15c18599971b7beced415a40f0c7deacfd9b0d1819e03d723d8bc943cfca"))
+    );
+}
+
+#[test]
+fn sign_and_verify() {
+    let signer = PrivateKeySigner::new(stark_felt!(
+        "0x4c1d4d3fb3bf6ea6a3a1a2f48c3d1a2b9b45a7d2c46ce4d0ba0b1fb7bd3f5e1"
+    ));
+    let hash = TransactionHash(stark_felt!("0x1234"));
+    let signature = signer.sign(&hash).unwrap();
+    assert_eq!(signature.0.len(), 2);
+    // Signing is deterministic.
+    assert_eq!(signer.sign(&hash).unwrap(), signature);
+
+    let public_key = signer.public_key();
+    assert!(verify_transaction_signature(&public_key, &hash, &signature).unwrap());
+    let other_hash = TransactionHash(stark_felt!("0x1235"));
+    assert!(!verify_transaction_signature(&public_key, &other_hash, &signature).unwrap());
+    let other_public_key = PrivateKeySigner::new(stark_felt!("0x2")).public_key();
+    assert!(!verify_transaction_signature(&other_public_key, &hash, &signature).unwrap());
+}
+
+#[test]
+fn malformed_signature() {
+    let public_key = PrivateKeySigner::new(stark_felt!("0x1")).public_key();
+    let hash = TransactionHash(stark_felt!("0x1234"));
+    assert_matches!(
+        verify_transaction_signature(&public_key, &hash, &TransactionSignature(vec![])),
+        Err(StarknetApiError::InvalidSignature(_))
+    );
+    let zero_signature = TransactionSignature(vec![stark_felt!("0x0"), stark_felt!("0x1")]);
+    assert_matches!(
+        verify_transaction_signature(&public_key, &hash, &zero_signature),
+        Err(StarknetApiError::InvalidSignature(_))
+    );
+}
@@ -0,0 +1,371 @@
+//! The encoding of a state diff that is published on L1 for data availability, in blobs or in
+//! calldata, and the stateless compression that is applied to it since Starknet v0.13.3.
+//!
+//! The encoded state diff is a list of felts: the number of contracts, then for each contract its
+//! address, a header that packs whether its class was updated, its nonce and its number of storage
+//! updates, the class hash if it was updated and the storage updates as key-value pairs, and then
+//! the number of declared classes and the class hash and compiled class hash of each class.
+#[cfg(test)]
+#[path = "data_availability_test.rs"]
+mod data_availability_test;
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use indexmap::{IndexMap, IndexSet};
+use primitive_types::U256;
+
+use crate::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use crate::hash::StarkFelt;
+use crate::patricia_trie::felt_as_u256;
+use crate::state::{StorageKey, ThinStateDiff};
+use crate::StarknetApiError;
+
+const NONCE_BITS: usize = 64;
+const N_UPDATES_BITS: usize = 64;
+const N_UPDATES_SMALL_BITS: usize = 8;
+
+const COMPRESSION_VERSION: u8 = 0;
+// The maximal number of bits that are packed into a felt.
+const MAX_N_BITS: usize = 251;
+const HEADER_ELM_BITS: usize = 20;
+// The unique values are kept in buckets by their size, and every other value is a pointer to a
+// unique value, in the last bucket.
+const UNIQUE_VALUE_BUCKET_BITS: [usize; 6] = [252, 125, 83, 62, 31, 15];
+const REPEATING_VALUE_BUCKET: usize = UNIQUE_VALUE_BUCKET_BITS.len();
+const N_BUCKETS: usize = UNIQUE_VALUE_BUCKET_BITS.len() + 1;
+// The version, the length of the data, the lengths of the unique value buckets and the number of
+// repeating values.
+const HEADER_LEN: usize = 1 + 1 + UNIQUE_VALUE_BUCKET_BITS.len() + 1;
+
+/// The version of the encoding of a state diff, which determines how the header of a contract is
+/// packed.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum DataAvailabilityVersion {
+    /// `class_updated << 128 | nonce << 64 | n_updates`, used up to v0.13.2.
+    V0_13_1,
+    /// `((class_updated << 64 | nonce) << n | n_updates) << 1 | is_n_updates_small`, where `n` is
+    /// 8 if the number of updates is less than 256 and 64 otherwise. Used since v0.13.3.
+    #[default]
+    V0_13_3,
+}
+
+/// Encodes a state diff for data availability. Contracts, storage keys and classes are sorted, and
+/// a contract whose nonce is not in the diff is encoded with nonce 0.
+pub fn encode_state_diff(
+    state_diff: &ThinStateDiff,
+    version: DataAvailabilityVersion,
+) -> Result<Vec<StarkFelt>, StarknetApiError> {
+    let class_updates = state_diff
+        .deployed_contracts
+        .iter()
+        .chain(&state_diff.replaced_classes)
+        .collect::<BTreeMap<_, _>>();
+    let contracts = class_updates
+        .keys()
+        .copied()
+        .chain(state_diff.storage_diffs.keys())
+        .chain(state_diff.nonces.keys())
+        .collect::<BTreeSet<_>>();
+
+    let mut data = vec![StarkFelt::from(contracts.len() as u64)];
+    for address in contracts {
+        let class_hash = class_updates.get(address);
+        let storage_diff = state_diff
+            .storage_diffs
+            .get(address)
+            .map(|storage_diff| storage_diff.iter().collect::<BTreeMap<_, _>>())
+            .unwrap_or_default();
+        let nonce = state_diff.nonces.get(address).copied().unwrap_or_default();
+        data.push(*address.0.key());
+        data.push(encode_contract_header(
+            class_hash.is_some(),
+            nonce,
+            storage_diff.len(),
+            version,
+        )?);
+        if let Some(class_hash) = class_hash {
+            data.push(class_hash.0);
+        }
+        for (key, value) in storage_diff {
+            data.extend([*key.0.key(), *value]);
+        }
+    }
+
+    let declared_classes = state_diff.declared_classes.iter().collect::<BTreeMap<_, _>>();
+    data.push(StarkFelt::from(declared_classes.len() as u64));
+    for (class_hash, compiled_class_hash) in declared_classes {
+        data.extend([class_hash.0, compiled_class_hash.0]);
+    }
+    Ok(data)
+}
+
+/// Decodes a state diff that was encoded for data availability.
+///
+/// The encoding does not distinguish deployed contracts from replaced classes, so all class
+/// updates are in `deployed_contracts`. Nonces that are 0 are not in the diff, since the nonce of
+/// every contract in the encoding is its new nonce, whether or not it changed.
+pub fn decode_state_diff(
+    data: &[StarkFelt],
+    version: DataAvailabilityVersion,
+) -> Result<ThinStateDiff, StarknetApiError> {
+    let mut data = data.iter().copied();
+    let mut state_diff = ThinStateDiff {
+        deployed_contracts: IndexMap::new(),
+        storage_diffs: IndexMap::new(),
+        declared_classes: IndexMap::new(),
+        deprecated_declared_classes: Vec::new(),
+        nonces: IndexMap::new(),
+        replaced_classes: IndexMap::new(),
+    };
+
+    let n_contracts = usize::try_from(next_felt(&mut data)?)?;
+    for _ in 0..n_contracts {
+        let address = ContractAddress::try_from(next_felt(&mut data)?)?;
+        let (class_updated, nonce, n_updates) =
+            decode_contract_header(next_felt(&mut data)?, version)?;
+        if class_updated {
+            state_diff.deployed_contracts.insert(address, ClassHash(next_felt(&mut data)?));
+        }
+        if nonce != Nonce::default() {
+            state_diff.nonces.insert(address, nonce);
+        }
+        if n_updates > 0 {
+            let mut storage_diff = IndexMap::new();
+            for _ in 0..n_updates {
+                let key = StorageKey::try_from(next_felt(&mut data)?)?;
+                storage_diff.insert(key, next_felt(&mut data)?);
+            }
+            state_diff.storage_diffs.insert(address, storage_diff);
+        }
+    }
+
+    let n_classes = usize::try_from(next_felt(&mut data)?)?;
+    for _ in 0..n_classes {
+        let class_hash = ClassHash(next_felt(&mut data)?);
+        state_diff.declared_classes.insert(class_hash, CompiledClassHash(next_felt(&mut data)?));
+    }
+    if data.next().is_some() {
+        return Err(invalid_encoding("unexpected elements at the end"));
+    }
+    Ok(state_diff)
+}
+
+/// Compresses data with the stateless compression of Starknet v0.13.3: every distinct value is
+/// packed once with as few bits as possible, and repeated values are replaced by pointers.
+pub fn compress(data: &[StarkFelt]) -> Result<Vec<StarkFelt>, StarknetApiError> {
+    if data.len() >= 1 << HEADER_ELM_BITS {
+        return Err(StarknetApiError::OutOfRange { string: data.len().to_string() });
+    }
+    let mut unique_values = vec![IndexSet::new(); UNIQUE_VALUE_BUCKET_BITS.len()];
+    let mut repeating_values = Vec::new();
+    let mut bucket_per_elm = Vec::with_capacity(data.len());
+    for value in data {
+        let value = felt_as_u256(value);
+        // The smallest bucket that the value fits in.
+        let bucket = (0..UNIQUE_VALUE_BUCKET_BITS.len())
+            .rev()
+            .find(|bucket| value.bits() <= UNIQUE_VALUE_BUCKET_BITS[*bucket])
+            .expect("A felt should fit in the largest bucket.");
+        let (index, is_new) = unique_values[bucket].insert_full(value);
+        if is_new {
+            bucket_per_elm.push(U256::from(bucket));
+        } else {
+            repeating_values.push((bucket, index));
+            bucket_per_elm.push(U256::from(REPEATING_VALUE_BUCKET));
+        }
+    }
+
+    let bucket_lengths = unique_values.iter().map(IndexSet::len).collect::<Vec<_>>();
+    let bucket_offsets = bucket_offsets(&bucket_lengths);
+    let n_unique_values: usize = bucket_lengths.iter().sum();
+    let header = [COMPRESSION_VERSION.into(), data.len()]
+        .into_iter()
+        .chain(bucket_lengths)
+        .chain([repeating_values.len()])
+        .map(U256::from)
+        .collect::<Vec<_>>();
+    let pointers = repeating_values
+        .into_iter()
+        .map(|(bucket, index)| U256::from(bucket_offsets[bucket] + index))
+        .collect::<Vec<_>>();
+
+    let mut compressed = pack_in_felts(&header, U256::one() << HEADER_ELM_BITS);
+    for (values, n_bits) in unique_values.into_iter().zip(UNIQUE_VALUE_BUCKET_BITS) {
+        compressed
+            .extend(pack_in_felts(&values.into_iter().collect::<Vec<_>>(), U256::one() << n_bits));
+    }
+    compressed.extend(pack_in_felts(&pointers, U256::from(n_unique_values)));
+    compressed.extend(pack_in_felts(&bucket_per_elm, U256::from(N_BUCKETS)));
+    Ok(compressed)
+}
+
+/// Decompresses data that was compressed with [`compress`].
+pub fn decompress(compressed: &[StarkFelt]) -> Result<Vec<StarkFelt>, StarknetApiError> {
+    let mut compressed = compressed.iter().copied();
+    let header = unpack_felts(&mut compressed, HEADER_LEN, U256::one() << HEADER_ELM_BITS)?
+        .into_iter()
+        .map(|elm| elm.as_usize())
+        .collect::<Vec<_>>();
+    if header[0] != usize::from(COMPRESSION_VERSION) {
+        return Err(invalid_encoding(&format!("unsupported compression version {}", header[0])));
+    }
+    let data_len = header[1];
+    let bucket_lengths = &header[2..HEADER_LEN];
+
+    let mut values = Vec::new();
+    for (length, n_bits) in bucket_lengths.iter().zip(UNIQUE_VALUE_BUCKET_BITS) {
+        values.extend(unpack_felts(&mut compressed, *length, U256::one() << n_bits)?);
+    }
+    let n_unique_values = values.len();
+    let n_repeating_values = bucket_lengths[REPEATING_VALUE_BUCKET];
+    for pointer in unpack_felts(&mut compressed, n_repeating_values, U256::from(n_unique_values))? {
+        values.push(values[pointer.as_usize()]);
+    }
+    let bucket_per_elm = unpack_felts(&mut compressed, data_len, U256::from(N_BUCKETS))?;
+    if compressed.next().is_some() {
+        return Err(invalid_encoding("unexpected elements at the end"));
+    }
+
+    let bucket_offsets = bucket_offsets(bucket_lengths);
+    let mut next_indices = bucket_offsets.clone();
+    let mut data = Vec::with_capacity(data_len);
+    for bucket in bucket_per_elm {
+        let bucket = bucket.as_usize();
+        let index = next_indices[bucket];
+        if index >= bucket_offsets[bucket] + bucket_lengths[bucket] {
+            return Err(invalid_encoding("a bucket has too few values"));
+        }
+        data.push(u256_as_felt(values[index])?);
+        next_indices[bucket] += 1;
+    }
+    Ok(data)
+}
+
+fn encode_contract_header(
+    class_updated: bool,
+    nonce: Nonce,
+    n_updates: usize,
+    version: DataAvailabilityVersion,
+) -> Result<StarkFelt, StarknetApiError> {
+    let class_updated = U256::from(u8::from(class_updated));
+    let nonce_value = felt_as_u256(&nonce.0);
+    if nonce_value.bits() > NONCE_BITS {
+        return Err(StarknetApiError::OutOfRange { string: nonce.0.to_string() });
+    }
+    let n_updates = U256::from(n_updates);
+    let header = match version {
+        DataAvailabilityVersion::V0_13_1 => {
+            (((class_updated << NONCE_BITS) | nonce_value) << N_UPDATES_BITS) | n_updates
+        }
+        DataAvailabilityVersion::V0_13_3 => {
+            let is_n_updates_small = n_updates.bits() <= N_UPDATES_SMALL_BITS;
+            let n_updates_bits =
+                if is_n_updates_small { N_UPDATES_SMALL_BITS } else { N_UPDATES_BITS };
+            let header =
+                (((class_updated << NONCE_BITS) | nonce_value) << n_updates_bits) | n_updates;
+            (header << 1) | U256::from(u8::from(is_n_updates_small))
+        }
+    };
+    u256_as_felt(header)
+}
+
+// Returns whether the class was updated, the nonce and the number of storage updates.
+fn decode_contract_header(
+    header: StarkFelt,
+    version: DataAvailabilityVersion,
+) -> Result<(bool, Nonce, usize), StarknetApiError> {
+    let mut header = felt_as_u256(&header);
+    let n_updates_bits = match version {
+        DataAvailabilityVersion::V0_13_1 => N_UPDATES_BITS,
+        DataAvailabilityVersion::V0_13_3 => {
+            let is_n_updates_small = header.bit(0);
+            header >>= 1;
+            if is_n_updates_small { N_UPDATES_SMALL_BITS } else { N_UPDATES_BITS }
+        }
+    };
+    let n_updates = header & low_bits_mask(n_updates_bits);
+    header >>= n_updates_bits;
+    let nonce = header & low_bits_mask(NONCE_BITS);
+    header >>= NONCE_BITS;
+    if header > U256::one() {
+        return Err(invalid_encoding("invalid contract header"));
+    }
+    Ok((!header.is_zero(), Nonce(u256_as_felt(nonce)?), n_updates.as_usize()))
+}
+
+// Packs the elements, which are less than the bound, into as few felts as possible, least
+// significant first.
+fn pack_in_felts(elms: &[U256], elm_bound: U256) -> Vec<StarkFelt> {
+    elms.chunks(n_elms_per_felt(elm_bound))
+        .map(|chunk| {
+            let packed =
+                chunk.iter().rev().fold(U256::zero(), |packed, elm| packed * elm_bound + elm);
+            u256_as_felt(packed).expect("Packed elements should fit in a StarkFelt.")
+        })
+        .collect()
+}
+
+fn unpack_felts(
+    compressed: &mut impl Iterator<Item = StarkFelt>,
+    n_elms: usize,
+    elm_bound: U256,
+) -> Result<Vec<U256>, StarknetApiError> {
+    if n_elms > 0 && elm_bound.is_zero() {
+        return Err(invalid_encoding("pointers to an empty set of values"));
+    }
+    let n_elms_per_felt = n_elms_per_felt(elm_bound);
+    let mut elms = Vec::new();
+    while elms.len() < n_elms {
+        let mut packed = felt_as_u256(&next_felt(compressed)?);
+        for _ in 0..n_elms_per_felt.min(n_elms - elms.len()) {
+            let (rest, elm) = packed.div_mod(elm_bound);
+            elms.push(elm);
+            packed = rest;
+        }
+        if !packed.is_zero() {
+            return Err(invalid_encoding("a packed felt has too many elements"));
+        }
+    }
+    Ok(elms)
+}
+
+fn n_elms_per_felt(elm_bound: U256) -> usize {
+    if elm_bound <= U256::one() {
+        return MAX_N_BITS;
+    }
+    if elm_bound > U256::one() << (MAX_N_BITS / 2) {
+        return 1;
+    }
+    // The number of bits of an element is the ceiling of log2 of the bound.
+    MAX_N_BITS / (elm_bound - 1).bits()
+}
+
+// Returns the offset of each bucket in the concatenation of the buckets.
+fn bucket_offsets(bucket_lengths: &[usize]) -> Vec<usize> {
+    bucket_lengths
+        .iter()
+        .scan(0, |offset, length| {
+            let bucket_offset = *offset;
+            *offset += length;
+            Some(bucket_offset)
+        })
+        .collect()
+}
+
+fn low_bits_mask(n_bits: usize) -> U256 {
+    (U256::one() << n_bits) - 1
+}
+
+fn u256_as_felt(value: U256) -> Result<StarkFelt, StarknetApiError> {
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+    StarkFelt::new(bytes)
+}
+
+fn next_felt(data: &mut impl Iterator<Item = StarkFelt>) -> Result<StarkFelt, StarknetApiError> {
+    data.next().ok_or_else(|| invalid_encoding("unexpected end of data"))
+}
+
+fn invalid_encoding(reason: &str) -> StarknetApiError {
+    StarknetApiError::InvalidDataAvailabilityEncoding(reason.to_string())
+}
@@ -0,0 +1,212 @@
+use assert_matches::assert_matches;
+use indexmap::{indexmap, IndexMap};
+
+use crate::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce, PatriciaKey};
+use crate::data_availability::{
+    compress, decode_state_diff, decompress, encode_state_diff, DataAvailabilityVersion,
+};
+use crate::hash::{StarkFelt, StarkHash};
+use crate::state::{StorageKey, ThinStateDiff};
+use crate::{patricia_key, stark_felt, StarknetApiError};
+
+fn felts(values: &[&str]) -> Vec<StarkFelt> {
+    values.iter().map(|value| StarkFelt::try_from(*value).unwrap()).collect()
+}
+
+fn state_diff() -> ThinStateDiff {
+    let address = ContractAddress(patricia_key!("0x100"));
+    ThinStateDiff {
+        deployed_contracts: indexmap! { address => ClassHash(stark_felt!("0x10")) },
+        storage_diffs: indexmap! {
+            address => indexmap! {
+                StorageKey(patricia_key!("0x1")) => stark_felt!("0x11"),
+                StorageKey(patricia_key!("0x2")) => stark_felt!("0x12"),
+            },
+        },
+        declared_classes: indexmap! {
+            ClassHash(stark_felt!("0x20")) => CompiledClassHash(stark_felt!("0x21")),
+        },
+        deprecated_declared_classes: vec![],
+        nonces: indexmap! { address => Nonce(stark_felt!("0x3")) },
+        replaced_classes: indexmap! {},
+    }
+}
+
+#[test]
+fn encode_state_diff_v0_13_3() {
+    let encoded = felts(&[
+        "0x1",
+        "0x100",
+        "0x2000000000000000605",
+        "0x10",
+        "0x1",
+        "0x11",
+        "0x2",
+        "0x12",
+        "0x1",
+        "0x20",
+        "0x21",
+    ]);
+    assert_eq!(
+        encode_state_diff(&state_diff(), DataAvailabilityVersion::V0_13_3).unwrap(),
+        encoded
+    );
+    assert_eq!(
+        decode_state_diff(&encoded, DataAvailabilityVersion::V0_13_3).unwrap(),
+        state_diff()
+    );
+}
+
+#[test]
+fn encode_state_diff_v0_13_1() {
+    let encoded = felts(&[
+        "0x1",
+        "0x100",
+        "0x100000000000000030000000000000002",
+        "0x10",
+        "0x1",
+        "0x11",
+        "0x2",
+        "0x12",
+        "0x1",
+        "0x20",
+        "0x21",
+    ]);
+    assert_eq!(
+        encode_state_diff(&state_diff(), DataAvailabilityVersion::V0_13_1).unwrap(),
+        encoded
+    );
+    assert_eq!(
+        decode_state_diff(&encoded, DataAvailabilityVersion::V0_13_1).unwrap(),
+        state_diff()
+    );
+}
+
+#[test]
+fn encode_many_storage_updates() {
+    let address = ContractAddress(patricia_key!("0x100"));
+    let storage_diff = (1..=300_u64)
+        .map(|key| (StorageKey::try_from(StarkFelt::from(key)).unwrap(), StarkFelt::from(key)))
+        .collect::<IndexMap<_, _>>();
+    let mut diff = state_diff();
+    diff.deployed_contracts.clear();
+    diff.storage_diffs = indexmap! { address => storage_diff };
+
+    let encoded = encode_state_diff(&diff, DataAvailabilityVersion::V0_13_3).unwrap();
+    // The number of updates does not fit in 8 bits.
+    assert_eq!(encoded[2], stark_felt!("0x60000000000000258"));
+    assert_eq!(decode_state_diff(&encoded, DataAvailabilityVersion::V0_13_3).unwrap(), diff);
+}
+
+#[test]
+fn encoding_does_not_keep_the_kind_of_class_update() {
+    let mut diff = state_diff();
+    let address = ContractAddress(patricia_key!("0x50"));
+    diff.replaced_classes.insert(address, ClassHash(stark_felt!("0x30")));
+    diff.nonces.insert(address, Nonce(stark_felt!("0x0")));
+
+    let encoded = encode_state_diff(&diff, DataAvailabilityVersion::default()).unwrap();
+    let decoded = decode_state_diff(&encoded, DataAvailabilityVersion::default()).unwrap();
+    assert_eq!(
+        decoded.deployed_contracts,
+        indexmap! {
+            address => ClassHash(stark_felt!("0x30")),
+            ContractAddress(patricia_key!("0x100")) => ClassHash(stark_felt!("0x10")),
+        }
+    );
+    assert!(decoded.replaced_classes.is_empty());
+    // Nonces that are 0 are not decoded.
+    assert_eq!(decoded.nonces, state_diff().nonces);
+}
+
+#[test]
+fn invalid_state_diff_encoding() {
+    let mut diff = state_diff();
+    diff.nonces[0] = Nonce(stark_felt!("0x10000000000000000"));
+    assert_matches!(
+        encode_state_diff(&diff, DataAvailabilityVersion::V0_13_3),
+        Err(StarknetApiError::OutOfRange { .. })
+    );
+
+    let encoded = encode_state_diff(&state_diff(), DataAvailabilityVersion::V0_13_3).unwrap();
+    assert_matches!(
+        decode_state_diff(&encoded[..encoded.len() - 1], DataAvailabilityVersion::V0_13_3),
+        Err(StarknetApiError::InvalidDataAvailabilityEncoding(_))
+    );
+    let mut invalid_header = encoded.clone();
+    invalid_header[2] = stark_felt!("0x4000000000000000605");
+    assert_matches!(
+        decode_state_diff(&invalid_header, DataAvailabilityVersion::V0_13_3),
+        Err(StarknetApiError::InvalidDataAvailabilityEncoding(_))
+    );
+    let mut trailing = encoded;
+    trailing.push(StarkHash::default());
+    assert_matches!(
+        decode_state_diff(&trailing, DataAvailabilityVersion::V0_13_3),
+        Err(StarknetApiError::InvalidDataAvailabilityEncoding(_))
+    );
+}
+
+// The compressed data is derived by hand from the layout of the compression, not generated by
+// cairo-lang.
+#[test]
+fn compress_data() {
+    let data = felts(&["0x1", "0x1", "0x10000000000000000000000000"]);
+    let compressed = felts(&[
+        // The version 0, the length 3, the lengths of the unique value buckets and one repeating
+        // value, 20 bits each.
+        "0x10000100000000000000000001000000000300000",
+        // The unique values, in the 125-bit bucket and in the 15-bit bucket.
+        "0x10000000000000000000000000",
+        "0x1",
+        // The pointer of the repeating value.
+        "0x1",
+        // The buckets of the values: 5 + 6 * 7 + 1 * 7^2.
+        "0x60",
+    ]);
+    assert_eq!(compress(&data).unwrap(), compressed);
+    assert_eq!(decompress(&compressed).unwrap(), data);
+}
+
+#[test]
+fn compression_round_trip() {
+    let mut data = felts(&[
+        "0x0",
+        "0x800000000000011000000000000000000000000000000000000000000000000",
+        "0x7fff",
+        "0x8000",
+        "0x123456789abcdef",
+        "0x800000000000011000000000000000000000000000000000000000000000000",
+    ]);
+    data.extend((0..40_u64).map(|value| StarkFelt::from(value % 25)));
+    let encoded = encode_state_diff(&state_diff(), DataAvailabilityVersion::V0_13_3).unwrap();
+    data.extend(encoded.iter().copied());
+
+    let compressed = compress(&data).unwrap();
+    assert!(compressed.len() < data.len());
+    assert_eq!(decompress(&compressed).unwrap(), data);
+    assert_eq!(decompress(&compress(&[]).unwrap()).unwrap(), vec![]);
+}
+
+#[test]
+fn invalid_compressed_data() {
+    let compressed = compress(&felts(&["0x1", "0x1", "0x10000000000000000000000000"])).unwrap();
+    assert_matches!(
+        decompress(&compressed[..compressed.len() - 1]),
+        Err(StarknetApiError::InvalidDataAvailabilityEncoding(_))
+    );
+
+    let mut unsupported_version = compressed.clone();
+    unsupported_version[0] = stark_felt!("0x10000100000000000000000001000000000300001");
+    assert_matches!(
+        decompress(&unsupported_version),
+        Err(StarknetApiError::InvalidDataAvailabilityEncoding(_))
+    );
+
+    let mut trailing = compressed;
+    trailing.push(StarkHash::default());
+    assert_matches!(
+        decompress(&trailing),
+        Err(StarknetApiError::InvalidDataAvailabilityEncoding(_))
+    );
+}
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+#[cfg(feature = "sierra_compiler")]
 use cairo_lang_starknet::casm_contract_class::CasmContractEntryPoint;
 use serde::de::Error as DeserializationError;
 use serde::{Deserialize, Deserializer, Serialize};
@@ -69,6 +70,8 @@ pub struct FunctionAbiEntry {
     pub name: String,
     pub inputs: Vec<TypedParameter>,
     pub outputs: Vec<TypedParameter>,
+    #[serde(rename = "stateMutability", default, skip_serializing_if = "Option::is_none")]
+    pub state_mutability: Option<String>,
 }
 
 /// A struct abi entry.
@@ -129,6 +132,7 @@ pub struct EntryPoint {
     pub offset: EntryPointOffset,
 }
 
+#[cfg(feature = "sierra_compiler")]
 impl TryFrom<CasmContractEntryPoint> for EntryPoint {
     type Error = StarknetApiError;
 
@@ -0,0 +1,274 @@
+//! The [global state](https://docs.starknet.io/documentation/architecture_and_concepts/Network_Architecture/starknet-state/#the_state_commitment)
+//! commitment: the contract trie, the class trie and the storage trie of each contract.
+#[cfg(test)]
+#[path = "global_state_test.rs"]
+mod global_state_test;
+
+use std::collections::HashSet;
+
+use indexmap::IndexMap;
+use once_cell::sync::Lazy;
+use starknet_crypto::FieldElement;
+
+use crate::core::{ClassHash, CompiledClassHash, ContractAddress, GlobalRoot, Nonce, PatriciaKey};
+use crate::hash::{
+    pedersen_hash, poseidon_hash, poseidon_hash_many, Pedersen, Poseidon, StarkFelt, StarkHash,
+};
+use crate::patricia_trie::{PatriciaTrie, TrieStorage};
+use crate::state::{StateDiff, StorageKey, ThinStateDiff};
+use crate::storage_proof::{ContractData, StorageProof};
+use crate::StarknetApiError;
+
+static STARKNET_STATE_V0: Lazy<StarkFelt> = Lazy::new(|| {
+    StarkFelt::from(
+        FieldElement::from_byte_slice_be(b"STARKNET_STATE_V0")
+            .expect("STARKNET_STATE_V0 should fit in a FieldElement."),
+    )
+});
+static CONTRACT_CLASS_LEAF_V0: Lazy<StarkFelt> = Lazy::new(|| {
+    StarkFelt::from(
+        FieldElement::from_byte_slice_be(b"CONTRACT_CLASS_LEAF_V0")
+            .expect("CONTRACT_CLASS_LEAF_V0 should fit in a FieldElement."),
+    )
+});
+
+/// The state of a contract, whose hash is the leaf of the contract in the contract trie.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ContractState {
+    pub class_hash: ClassHash,
+    pub nonce: Nonce,
+    pub storage_root: StarkHash,
+}
+
+/// The roots of the tries of the global state.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct StateRoots {
+    pub contract_trie_root: StarkHash,
+    pub class_trie_root: StarkHash,
+    pub global_root: GlobalRoot,
+}
+
+/// The tries of the global state, whose nodes and contract states are kept in a [`TrieStorage`].
+pub struct GlobalStateTrie<S: TrieStorage> {
+    storage: S,
+    contract_trie_root: StarkHash,
+    class_trie_root: StarkHash,
+}
+
+impl<S: TrieStorage> GlobalStateTrie<S> {
+    /// Returns the tries of an empty state.
+    pub fn new(storage: S) -> Self {
+        Self::with_roots(storage, StarkHash::default(), StarkHash::default())
+    }
+
+    /// Returns the tries of the state with the given roots, whose nodes and contract states are in
+    /// the storage.
+    pub fn with_roots(
+        storage: S,
+        contract_trie_root: StarkHash,
+        class_trie_root: StarkHash,
+    ) -> Self {
+        Self { storage, contract_trie_root, class_trie_root }
+    }
+
+    pub fn storage(&self) -> &S {
+        &self.storage
+    }
+
+    pub fn roots(&self) -> StateRoots {
+        StateRoots {
+            contract_trie_root: self.contract_trie_root,
+            class_trie_root: self.class_trie_root,
+            global_root: calculate_global_root(self.contract_trie_root, self.class_trie_root),
+        }
+    }
+
+    /// Returns the state of a contract, which is the default state if the contract was not
+    /// deployed.
+    pub fn contract_state(
+        &self,
+        address: &ContractAddress,
+    ) -> Result<ContractState, StarknetApiError> {
+        Ok(self.deployed_contract_state(address)?.unwrap_or_default())
+    }
+
+    /// Returns the value of a storage key of a contract.
+    pub fn storage_value(
+        &self,
+        address: &ContractAddress,
+        key: &StorageKey,
+    ) -> Result<StarkFelt, StarknetApiError> {
+        let storage_root = self.contract_state(address)?.storage_root;
+        PatriciaTrie::<_, Pedersen>::new(&self.storage, storage_root).get(&key.0)
+    }
+
+    /// Returns a proof of the state of a contract and of the values of the given storage keys of
+    /// the contract.
+    pub fn get_proof(
+        &self,
+        address: &ContractAddress,
+        keys: &[StorageKey],
+    ) -> Result<StorageProof, StarknetApiError> {
+        let contract_proof =
+            PatriciaTrie::<_, Pedersen>::new(&self.storage, self.contract_trie_root)
+                .get_proof(&address.0)?;
+        let contract_data = match self.deployed_contract_state(address)? {
+            Some(state) => {
+                let storage_trie =
+                    PatriciaTrie::<_, Pedersen>::new(&self.storage, state.storage_root);
+                Some(ContractData {
+                    class_hash: state.class_hash,
+                    nonce: state.nonce,
+                    root: state.storage_root,
+                    contract_state_hash_version: StarkFelt::from(0_u8),
+                    storage_proofs: keys
+                        .iter()
+                        .map(|key| storage_trie.get_proof(&key.0))
+                        .collect::<Result<_, _>>()?,
+                })
+            }
+            None => None,
+        };
+        Ok(StorageProof {
+            class_commitment: self.class_trie_root,
+            state_commitment: self.contract_trie_root,
+            contract_proof,
+            contract_data,
+        })
+    }
+
+    // Returns the state of a contract from its leaf in the contract trie, or `None` if the contract
+    // has no leaf.
+    fn deployed_contract_state(
+        &self,
+        address: &ContractAddress,
+    ) -> Result<Option<ContractState>, StarknetApiError> {
+        let hash = PatriciaTrie::<_, Pedersen>::new(&self.storage, self.contract_trie_root)
+            .get(&address.0)?;
+        if hash == StarkHash::default() {
+            return Ok(None);
+        }
+        self.storage
+            .get_contract_state(&hash)
+            .map(Some)
+            .ok_or(StarknetApiError::MissingContractState { hash })
+    }
+
+    /// Applies a state diff and returns the new roots.
+    pub fn apply_state_diff(&mut self, diff: &StateDiff) -> Result<StateRoots, StarknetApiError> {
+        let declared_classes = diff
+            .declared_classes
+            .iter()
+            .map(|(class_hash, (compiled_class_hash, _))| (*class_hash, *compiled_class_hash))
+            .collect();
+        self.apply(
+            &diff.deployed_contracts,
+            &diff.storage_diffs,
+            &declared_classes,
+            &diff.nonces,
+            &diff.replaced_classes,
+        )
+    }
+
+    /// Applies a thin state diff and returns the new roots.
+    pub fn apply_thin_state_diff(
+        &mut self,
+        diff: &ThinStateDiff,
+    ) -> Result<StateRoots, StarknetApiError> {
+        self.apply(
+            &diff.deployed_contracts,
+            &diff.storage_diffs,
+            &diff.declared_classes,
+            &diff.nonces,
+            &diff.replaced_classes,
+        )
+    }
+
+    fn apply(
+        &mut self,
+        deployed_contracts: &IndexMap<ContractAddress, ClassHash>,
+        storage_diffs: &IndexMap<ContractAddress, IndexMap<StorageKey, StarkFelt>>,
+        declared_classes: &IndexMap<ClassHash, CompiledClassHash>,
+        nonces: &IndexMap<ContractAddress, Nonce>,
+        replaced_classes: &IndexMap<ContractAddress, ClassHash>,
+    ) -> Result<StateRoots, StarknetApiError> {
+        let touched_contracts = deployed_contracts
+            .keys()
+            .chain(storage_diffs.keys())
+            .chain(nonces.keys())
+            .chain(replaced_classes.keys())
+            .copied()
+            .collect::<HashSet<_>>();
+
+        let mut contract_leaves = Vec::with_capacity(touched_contracts.len());
+        for address in touched_contracts {
+            let mut state = self.contract_state(&address)?;
+            if let Some(class_hash) =
+                deployed_contracts.get(&address).or_else(|| replaced_classes.get(&address))
+            {
+                state.class_hash = *class_hash;
+            }
+            if let Some(nonce) = nonces.get(&address) {
+                state.nonce = *nonce;
+            }
+            if let Some(storage_diff) = storage_diffs.get(&address) {
+                state.storage_root =
+                    PatriciaTrie::<_, Pedersen>::new(&mut self.storage, state.storage_root)
+                        .update(storage_diff.iter().map(|(key, value)| (key.0, *value)))?;
+            }
+            let hash =
+                calculate_contract_state_hash(&state.class_hash, &state.storage_root, &state.nonce);
+            self.storage.set_contract_state(hash, state);
+            contract_leaves.push((address.0, hash));
+        }
+        self.contract_trie_root =
+            PatriciaTrie::<_, Pedersen>::new(&mut self.storage, self.contract_trie_root)
+                .update(contract_leaves)?;
+
+        let class_leaves = declared_classes
+            .iter()
+            .map(|(class_hash, compiled_class_hash)| {
+                Ok((
+                    PatriciaKey::try_from(class_hash.0)?,
+                    calculate_class_leaf_hash(compiled_class_hash),
+                ))
+            })
+            .collect::<Result<Vec<_>, StarknetApiError>>()?;
+        self.class_trie_root =
+            PatriciaTrie::<_, Poseidon>::new(&mut self.storage, self.class_trie_root)
+                .update(class_leaves)?;
+
+        Ok(self.roots())
+    }
+}
+
+/// Calculates the global root from the roots of the contract trie and the class trie. Before the
+/// class trie existed, the global root was the root of the contract trie.
+pub fn calculate_global_root(
+    contract_trie_root: StarkHash,
+    class_trie_root: StarkHash,
+) -> GlobalRoot {
+    if class_trie_root == StarkHash::default() {
+        return GlobalRoot(contract_trie_root);
+    }
+    GlobalRoot(poseidon_hash_many(&[*STARKNET_STATE_V0, contract_trie_root, class_trie_root]))
+}
+
+/// Calculates the leaf of a contract in the contract trie:
+/// `H(H(H(class_hash, storage_root), nonce), 0)`, where `H` is the Pedersen hash and 0 is the
+/// version of the contract state hash.
+pub fn calculate_contract_state_hash(
+    class_hash: &ClassHash,
+    storage_root: &StarkHash,
+    nonce: &Nonce,
+) -> StarkHash {
+    let hash = pedersen_hash(&class_hash.0, storage_root);
+    let hash = pedersen_hash(&hash, &nonce.0);
+    pedersen_hash(&hash, &StarkFelt::from(0_u8))
+}
+
+/// Calculates the leaf of a class in the class trie:
+/// `H("CONTRACT_CLASS_LEAF_V0", compiled_class_hash)`, where `H` is the Poseidon hash.
+pub fn calculate_class_leaf_hash(compiled_class_hash: &CompiledClassHash) -> StarkHash {
+    poseidon_hash(&CONTRACT_CLASS_LEAF_V0, &compiled_class_hash.0)
+}
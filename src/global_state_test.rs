@@ -0,0 +1,201 @@
+use assert_matches::assert_matches;
+use indexmap::indexmap;
+use starknet_crypto::FieldElement;
+
+use crate::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce, PatriciaKey};
+use crate::global_state::{
+    calculate_class_leaf_hash, calculate_contract_state_hash, ContractState, GlobalStateTrie,
+};
+use crate::hash::{
+    pedersen_hash, poseidon_hash, poseidon_hash_many, Pedersen, Poseidon, StarkFelt, StarkHash,
+};
+use crate::patricia_trie::{InMemoryTrieStorage, PatriciaTrie};
+use crate::state::{StateDiff, StorageKey, ThinStateDiff};
+use crate::{patricia_key, stark_felt, StarknetApiError};
+
+fn state_diff() -> ThinStateDiff {
+    let address = ContractAddress(patricia_key!("0x100"));
+    ThinStateDiff {
+        deployed_contracts: indexmap! { address => ClassHash(stark_felt!("0x10")) },
+        storage_diffs: indexmap! {
+            address => indexmap! { StorageKey(patricia_key!("0x5")) => stark_felt!("0x55") },
+        },
+        declared_classes: indexmap! {
+            ClassHash(stark_felt!("0x20")) => CompiledClassHash(stark_felt!("0x21")),
+        },
+        deprecated_declared_classes: vec![ClassHash(stark_felt!("0x10"))],
+        nonces: indexmap! { address => Nonce(stark_felt!("0x1")) },
+        replaced_classes: indexmap! {},
+    }
+}
+
+#[test]
+fn contract_state_hash() {
+    let class_hash = ClassHash(stark_felt!("0x10"));
+    let storage_root = stark_felt!("0x20");
+    let nonce = Nonce(stark_felt!("0x1"));
+    let expected = pedersen_hash(
+        &pedersen_hash(&pedersen_hash(&class_hash.0, &storage_root), &nonce.0),
+        &stark_felt!("0x0"),
+    );
+    assert_eq!(calculate_contract_state_hash(&class_hash, &storage_root, &nonce), expected);
+    // The nesting is not symmetric in the storage root and the nonce.
+    assert_ne!(
+        calculate_contract_state_hash(&class_hash, &nonce.0, &Nonce(storage_root)),
+        expected
+    );
+}
+
+#[test]
+fn class_leaf_hash() {
+    let compiled_class_hash = CompiledClassHash(stark_felt!("0x21"));
+    let expected = poseidon_hash(
+        &FieldElement::from_byte_slice_be(b"CONTRACT_CLASS_LEAF_V0").unwrap().into(),
+        &compiled_class_hash.0,
+    );
+    assert_eq!(calculate_class_leaf_hash(&compiled_class_hash), expected);
+}
+
+#[test]
+fn empty_state() {
+    let state = GlobalStateTrie::new(InMemoryTrieStorage::default());
+    assert_eq!(state.roots().global_root.0, StarkHash::default());
+}
+
+#[test]
+fn apply_state_diff() {
+    let mut state = GlobalStateTrie::new(InMemoryTrieStorage::default());
+    let roots = state.apply_thin_state_diff(&state_diff()).unwrap();
+
+    let address = ContractAddress(patricia_key!("0x100"));
+    let mut storage = InMemoryTrieStorage::default();
+    let storage_root = PatriciaTrie::<_, Pedersen>::new(&mut storage, StarkHash::default())
+        .update([(patricia_key!("0x5"), stark_felt!("0x55"))])
+        .unwrap();
+    assert_eq!(
+        state.contract_state(&address).unwrap(),
+        ContractState {
+            class_hash: ClassHash(stark_felt!("0x10")),
+            nonce: Nonce(stark_felt!("0x1")),
+            storage_root,
+        }
+    );
+    assert_eq!(
+        state.storage_value(&address, &StorageKey(patricia_key!("0x5"))).unwrap(),
+        stark_felt!("0x55")
+    );
+
+    let contract_trie_root = PatriciaTrie::<_, Pedersen>::new(&mut storage, StarkHash::default())
+        .update([(
+            address.0,
+            calculate_contract_state_hash(
+                &ClassHash(stark_felt!("0x10")),
+                &storage_root,
+                &Nonce(stark_felt!("0x1")),
+            ),
+        )])
+        .unwrap();
+    assert_eq!(roots.contract_trie_root, contract_trie_root);
+
+    let class_leaf = calculate_class_leaf_hash(&CompiledClassHash(stark_felt!("0x21")));
+    let class_trie_root = PatriciaTrie::<_, Poseidon>::new(&mut storage, StarkHash::default())
+        .update([(patricia_key!("0x20"), class_leaf)])
+        .unwrap();
+    assert_eq!(roots.class_trie_root, class_trie_root);
+
+    let global_root = poseidon_hash_many(&[
+        FieldElement::from_byte_slice_be(b"STARKNET_STATE_V0").unwrap().into(),
+        contract_trie_root,
+        class_trie_root,
+    ]);
+    assert_eq!(roots.global_root.0, global_root);
+}
+
+#[test]
+fn global_root_without_classes() {
+    let mut diff = state_diff();
+    diff.declared_classes.clear();
+    let mut state = GlobalStateTrie::new(InMemoryTrieStorage::default());
+    let roots = state.apply_thin_state_diff(&diff).unwrap();
+    assert_eq!(roots.class_trie_root, StarkHash::default());
+    assert_eq!(roots.global_root.0, roots.contract_trie_root);
+}
+
+#[test]
+fn state_diffs_are_applied_on_top_of_each_other() {
+    let mut state = GlobalStateTrie::new(InMemoryTrieStorage::default());
+    state.apply_thin_state_diff(&state_diff()).unwrap();
+    let address = ContractAddress(patricia_key!("0x100"));
+    let second_diff = StateDiff {
+        storage_diffs: indexmap! {
+            address => indexmap! { StorageKey(patricia_key!("0x6")) => stark_felt!("0x66") },
+        },
+        replaced_classes: indexmap! { address => ClassHash(stark_felt!("0x20")) },
+        ..Default::default()
+    };
+    state.apply_state_diff(&second_diff).unwrap();
+
+    let contract_state = state.contract_state(&address).unwrap();
+    assert_eq!(contract_state.class_hash, ClassHash(stark_felt!("0x20")));
+    assert_eq!(contract_state.nonce, Nonce(stark_felt!("0x1")));
+    assert_eq!(
+        state.storage_value(&address, &StorageKey(patricia_key!("0x5"))).unwrap(),
+        stark_felt!("0x55")
+    );
+
+    // Applying both diffs at once gives the same roots.
+    let mut merged_diff = state_diff();
+    merged_diff.storage_diffs[&address]
+        .insert(StorageKey(patricia_key!("0x6")), stark_felt!("0x66"));
+    merged_diff.replaced_classes.insert(address, ClassHash(stark_felt!("0x20")));
+    merged_diff.deployed_contracts.clear();
+    let mut merged_state = GlobalStateTrie::new(InMemoryTrieStorage::default());
+    assert_eq!(merged_state.apply_thin_state_diff(&merged_diff).unwrap(), state.roots());
+}
+
+#[test]
+fn state_is_read_back_from_its_roots() {
+    let mut state = GlobalStateTrie::new(InMemoryTrieStorage::default());
+    let roots = state.apply_thin_state_diff(&state_diff()).unwrap();
+    let address = ContractAddress(patricia_key!("0x100"));
+
+    let mut reopened_state = GlobalStateTrie::with_roots(
+        state.storage().clone(),
+        roots.contract_trie_root,
+        roots.class_trie_root,
+    );
+    assert_eq!(reopened_state.roots(), roots);
+    assert_eq!(
+        reopened_state.contract_state(&address).unwrap(),
+        state.contract_state(&address).unwrap()
+    );
+    assert_eq!(
+        reopened_state.storage_value(&address, &StorageKey(patricia_key!("0x5"))).unwrap(),
+        stark_felt!("0x55")
+    );
+    assert_eq!(
+        reopened_state.contract_state(&ContractAddress(patricia_key!("0x200"))).unwrap(),
+        ContractState::default()
+    );
+
+    let second_diff = StateDiff {
+        nonces: indexmap! { address => Nonce(stark_felt!("0x2")) },
+        ..Default::default()
+    };
+    assert_eq!(
+        reopened_state.apply_state_diff(&second_diff).unwrap(),
+        state.apply_state_diff(&second_diff).unwrap()
+    );
+
+    // A storage with the nodes of the contract trie but without the contract states.
+    let mut nodes = InMemoryTrieStorage::default();
+    let contract_trie_root = PatriciaTrie::<_, Pedersen>::new(&mut nodes, StarkHash::default())
+        .update([(address.0, stark_felt!("0x1234"))])
+        .unwrap();
+    let state_without_contract_states =
+        GlobalStateTrie::with_roots(nodes, contract_trie_root, StarkHash::default());
+    assert_matches!(
+        state_without_contract_states.contract_state(&address),
+        Err(StarknetApiError::MissingContractState { hash }) if hash == stark_felt!("0x1234")
+    );
+}
@@ -6,7 +6,11 @@ use std::fmt::{Debug, Display};
 use std::io::Error;
 
 use serde::{Deserialize, Serialize};
-use starknet_crypto::{pedersen_hash as starknet_crypto_pedersen_hash, FieldElement};
+use sha3::{Digest, Keccak256};
+use starknet_crypto::{
+    pedersen_hash as starknet_crypto_pedersen_hash, poseidon_hash as starknet_crypto_poseidon_hash,
+    poseidon_hash_many as starknet_crypto_poseidon_hash_many, FieldElement,
+};
 
 use crate::serde_utils::{
     bytes_from_hex_str, hex_str_from_bytes, BytesAsHex, NonPrefixedBytesAsHex, PrefixedBytesAsHex,
@@ -43,6 +47,68 @@ pub fn pedersen_hash_array(felts: &[StarkFelt]) -> StarkHash {
     pedersen_hash(&current_hash, &data_len)
 }
 
+/// Computes Poseidon hash on two elements, as defined
+/// in <https://docs.starknet.io/documentation/architecture_and_concepts/Hashing/hash-functions/#poseidon_hash.>
+pub fn poseidon_hash(felt0: &StarkFelt, felt1: &StarkFelt) -> StarkHash {
+    StarkFelt::from(starknet_crypto_poseidon_hash(
+        FieldElement::from(*felt0),
+        FieldElement::from(*felt1),
+    ))
+}
+
+/// Computes Poseidon hash on an array of elements, using the sponge construction defined
+/// in <https://docs.starknet.io/documentation/architecture_and_concepts/Hashing/hash-functions/#poseidon_array_hash.>
+pub fn poseidon_hash_many(felts: &[StarkFelt]) -> StarkHash {
+    let felts = felts.iter().map(|felt| FieldElement::from(*felt)).collect::<Vec<_>>();
+    StarkFelt::from(starknet_crypto_poseidon_hash_many(&felts))
+}
+
+/// Computes the Starknet Keccak hash: the Keccak-256 hash of the data, truncated to its 250 least
+/// significant bits, as defined
+/// in <https://docs.starknet.io/documentation/architecture_and_concepts/Hashing/hash-functions/#starknet_keccak.>
+pub fn starknet_keccak(data: &[u8]) -> StarkHash {
+    let mut bytes: [u8; 32] = Keccak256::digest(data).into();
+    bytes[0] &= 0x03;
+    StarkFelt(bytes)
+}
+
+/// A hash function on field elements, used where Starknet hashes with either Pedersen or
+/// Poseidon.
+pub trait HashFunction {
+    /// Hashes two elements.
+    fn hash(felt0: &StarkFelt, felt1: &StarkFelt) -> StarkHash;
+    /// Hashes an array of elements.
+    fn hash_array(felts: &[StarkFelt]) -> StarkHash;
+}
+
+/// The [Pedersen hash](`pedersen_hash`).
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Pedersen;
+
+impl HashFunction for Pedersen {
+    fn hash(felt0: &StarkFelt, felt1: &StarkFelt) -> StarkHash {
+        pedersen_hash(felt0, felt1)
+    }
+
+    fn hash_array(felts: &[StarkFelt]) -> StarkHash {
+        pedersen_hash_array(felts)
+    }
+}
+
+/// The [Poseidon hash](`poseidon_hash`).
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Poseidon;
+
+impl HashFunction for Poseidon {
+    fn hash(felt0: &StarkFelt, felt1: &StarkFelt) -> StarkHash {
+        poseidon_hash(felt0, felt1)
+    }
+
+    fn hash_array(felts: &[StarkFelt]) -> StarkHash {
+        poseidon_hash_many(felts)
+    }
+}
+
 // TODO: Move to a different crate.
 /// The StarkNet [field element](https://docs.starknet.io/documentation/architecture_and_concepts/Hashing/hash-functions/#domain_and_range).
 #[derive(Copy, Clone, Eq, PartialEq, Default, Hash, Deserialize, Serialize, PartialOrd, Ord)]
@@ -59,6 +125,11 @@ impl StarkFelt {
         Err(StarknetApiError::OutOfRange { string: hex_str_from_bytes::<32, true>(bytes) })
     }
 
+    // Returns a [`StarkFelt`] without checking that the bytes are in range, for constants.
+    pub(crate) const fn from_bytes_unchecked(bytes: [u8; 32]) -> StarkFelt {
+        Self(bytes)
+    }
+
     /// Storage efficient serialization for field elements.
     pub fn serialize(&self, res: &mut impl std::io::Write) -> Result<(), Error> {
         // We use the fact that bytes[0] < 0x10 and encode the size of the felt in the 4 most
@@ -1,4 +1,7 @@
-use crate::hash::{pedersen_hash, pedersen_hash_array, StarkFelt};
+use crate::hash::{
+    pedersen_hash, pedersen_hash_array, poseidon_hash, poseidon_hash_many, starknet_keccak,
+    HashFunction, Pedersen, Poseidon, StarkFelt,
+};
 use crate::stark_felt;
 use crate::transaction::Fee;
 
@@ -24,6 +27,51 @@ fn pedersen_hash_array_correctness() {
     assert_eq!(pedersen_hash_array(&[a, b, c]), expected);
 }
 
+#[test]
+fn poseidon_hash_correctness() {
+    // Test vectors generated from cairo-lang v0.11.0.
+    let a = stark_felt!("0xb662f9017fa7956fd70e26129b1833e10ad000fd37b4d9f4e0ce6884b7bbe");
+    let b = stark_felt!("0x1fe356bf76102cdae1bfbdc173602ead228b12904c00dad9cf16e035468bea");
+    let expected = stark_felt!("0x75540825a6ecc5dc7d7c2f5f868164182742227f1367d66c43ee51ec7937a81");
+    assert_eq!(poseidon_hash(&a, &b), expected);
+}
+
+#[test]
+fn poseidon_hash_many_correctness() {
+    // Test vectors generated from cairo-lang v0.11.0.
+    let felts = [
+        stark_felt!("0x9bf52404586087391c5fbb42538692e7ca2149bac13c145ae4230a51a6fc47"),
+        stark_felt!("0x40304159ee9d2d611120fbd7c7fb8020cc8f7a599bfa108e0e085222b862c0"),
+        stark_felt!("0x46286e4f3c450761d960d6a151a9c0988f9e16f8a48d4c0a85817c009f806a"),
+    ];
+    let expected = stark_felt!("0x1ec38b38dc88bac7b0ed6ff6326f975a06a59ac601b417745fd412a5d38e4f7");
+    assert_eq!(poseidon_hash_many(&felts), expected);
+}
+
+#[test]
+fn starknet_keccak_correctness() {
+    assert_eq!(
+        starknet_keccak(b"constructor"),
+        stark_felt!("0x28ffe4ff0f226a9107253e17a904099aa4f63a02a5621de0576e5aa71bc5194")
+    );
+    // The Keccak-256 hash of the empty string is
+    // 0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470.
+    assert_eq!(
+        starknet_keccak(b""),
+        stark_felt!("0x1d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470")
+    );
+}
+
+#[test]
+fn hash_function_trait() {
+    let a = stark_felt!("0xaa");
+    let b = stark_felt!("0xbb");
+    assert_eq!(Pedersen::hash(&a, &b), pedersen_hash(&a, &b));
+    assert_eq!(Pedersen::hash_array(&[a, b]), pedersen_hash_array(&[a, b]));
+    assert_eq!(Poseidon::hash(&a, &b), poseidon_hash(&a, &b));
+    assert_eq!(Poseidon::hash_array(&[a, b]), poseidon_hash_many(&[a, b]));
+}
+
 #[test]
 fn hash_macro() {
     assert_eq!(
@@ -3,16 +3,35 @@
 //! [`Starknet`]: https://starknet.io/
 
 pub mod block;
+pub mod block_commitment;
+pub mod block_hash;
+pub mod class_hash;
+pub mod compiled_class;
 pub mod core;
+pub mod crypto;
+pub mod data_availability;
 pub mod deprecated_contract_class;
+pub mod global_state;
 pub mod hash;
+pub mod messaging;
+pub mod multicall;
+pub mod os_output;
+pub mod patricia_trie;
 pub mod serde_utils;
+#[cfg(feature = "sierra_compiler")]
+pub mod sierra_compiler;
 pub mod state;
+pub mod storage_proof;
+#[cfg(test)]
+pub(crate) mod test_utils;
 pub mod transaction;
+pub mod transaction_builder;
+pub mod transaction_hash;
 pub mod type_utils;
 
 use std::num::ParseIntError;
 
+use hash::{StarkFelt, StarkHash};
 use serde_utils::InnerDeserializationError;
 
 /// The error type returned by StarknetApi.
@@ -27,4 +46,52 @@ pub enum StarknetApiError {
     /// Error when serializing into number.
     #[error(transparent)]
     ParseIntError(#[from] ParseIntError),
+    /// The bytecode segment lengths of a compiled class do not sum up to its bytecode length.
+    #[error("Invalid bytecode segment lengths for bytecode of length {bytecode_length}.")]
+    InvalidBytecodeSegmentLengths { bytecode_length: usize },
+    /// The program of a deprecated contract class does not have the expected format.
+    #[error("Invalid program: {0}.")]
+    InvalidProgram(String),
+    /// A contract class cannot be hashed, or cannot be converted into a contract class of the
+    /// Sierra compiler.
+    #[error("Invalid contract class: {0}.")]
+    InvalidContractClass(String),
+    /// A compiled class cannot be converted into a
+    /// [CompiledContractClass](`crate::compiled_class::CompiledContractClass`).
+    #[error("Invalid compiled class: {0}.")]
+    InvalidCompiledClass(String),
+    /// A node of a Patricia trie is missing from its storage.
+    #[error("Missing trie node {hash}.")]
+    MissingTrieNode { hash: StarkHash },
+    /// A contract state that is a leaf of the contract trie is missing from the trie storage.
+    #[error("Missing contract state {hash}.")]
+    MissingContractState { hash: StarkHash },
+    /// The version of a transaction does not match its type.
+    #[error("Invalid transaction version {version}, expected {expected}.")]
+    InvalidTransactionVersion { version: StarkFelt, expected: StarkFelt },
+    /// A transaction hash could not be signed.
+    #[error("Signing failed: {0}.")]
+    SigningFailed(String),
+    /// A signature or a public key is malformed.
+    #[error("Invalid signature: {0}.")]
+    InvalidSignature(String),
+    /// A required field of a transaction was not set in its builder.
+    #[error("Missing transaction field {field}.")]
+    MissingTransactionField { field: &'static str },
+    /// The calldata of an account does not encode a list of calls.
+    #[error("Invalid multicall calldata: {0}.")]
+    InvalidMulticallCalldata(String),
+    /// The calldata of an L1 handler transaction does not start with the address of the sender of
+    /// the message on L1.
+    #[error("Missing L1 sender address in the calldata of an L1 handler transaction.")]
+    MissingL1SenderAddress,
+    /// A log of the Starknet core contract on Ethereum is malformed.
+    #[error("Invalid Ethereum log: {0}.")]
+    InvalidEthereumLog(String),
+    /// The output of the Starknet OS is malformed or has an unsupported layout.
+    #[error("Invalid Starknet OS output: {0}.")]
+    InvalidOsOutput(String),
+    /// A state diff or compressed data that is published for data availability is malformed.
+    #[error("Invalid data availability encoding: {0}.")]
+    InvalidDataAvailabilityEncoding(String),
 }
@@ -0,0 +1,175 @@
+//! Messages between Ethereum and Starknet, as emitted in the logs of the Starknet core contract.
+#[cfg(test)]
+#[path = "messaging_test.rs"]
+mod messaging_test;
+
+use once_cell::sync::Lazy;
+use primitive_types::H256;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+
+use crate::core::{ContractAddress, EntryPointSelector, Nonce};
+use crate::hash::StarkFelt;
+use crate::transaction::{EthAddress, Fee, L1ToL2Payload, L2ToL1Payload, MessageToL1, MessageToL2};
+use crate::StarknetApiError;
+
+/// The topic of `LogMessageToL1(uint256 indexed fromAddress, address indexed toAddress,
+/// uint256[] payload)`.
+pub static LOG_MESSAGE_TO_L1_TOPIC: Lazy<H256> =
+    Lazy::new(|| H256(Keccak256::digest(b"LogMessageToL1(uint256,address,uint256[])").into()));
+/// The topic of `LogMessageToL2(address indexed fromAddress, uint256 indexed toAddress,
+/// uint256 indexed selector, uint256[] payload, uint256 nonce, uint256 fee)`.
+pub static LOG_MESSAGE_TO_L2_TOPIC: Lazy<H256> = Lazy::new(|| {
+    H256(
+        Keccak256::digest(b"LogMessageToL2(address,uint256,uint256,uint256[],uint256,uint256)")
+            .into(),
+    )
+});
+
+/// A log of an Ethereum contract: its topics, the first of which identifies the event, and its
+/// ABI-encoded data.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+pub struct EthereumLog {
+    pub topics: Vec<H256>,
+    pub data: Vec<u8>,
+}
+
+/// A message sent from L1 to a contract on L2, with the fields that the Starknet core contract
+/// assigns to it.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+pub struct L1ToL2Message {
+    pub message: MessageToL2,
+    pub to_address: ContractAddress,
+    pub selector: EntryPointSelector,
+    pub nonce: Nonce,
+    /// The fee paid on L1 for handling the message on L2, in WEI.
+    pub fee: Fee,
+}
+
+impl L1ToL2Message {
+    /// Returns the hash of the message, as computed by the Starknet core contract.
+    pub fn hash(&self) -> H256 {
+        self.message.hash(self.nonce, self.to_address, self.selector)
+    }
+}
+
+/// Parses a `LogMessageToL2` log.
+impl TryFrom<&EthereumLog> for L1ToL2Message {
+    type Error = StarknetApiError;
+
+    fn try_from(log: &EthereumLog) -> Result<Self, Self::Error> {
+        let [topic, from_address, to_address, selector] = log.topics[..] else {
+            return Err(invalid_log(format!(
+                "expected 4 topics in LogMessageToL2, found {}",
+                log.topics.len()
+            )));
+        };
+        check_topic(topic, *LOG_MESSAGE_TO_L2_TOPIC, "LogMessageToL2")?;
+        // The data is `(payload, nonce, fee)`.
+        let data = AbiData::new(&log.data)?;
+        let payload = data.array(0)?;
+        let nonce = Nonce(data.felt(1)?);
+        let fee = Fee(data.u128(2)?);
+        Ok(Self {
+            message: MessageToL2 {
+                from_address: EthAddress::try_from(word_as_felt(from_address)?)?,
+                payload: L1ToL2Payload(payload),
+            },
+            to_address: ContractAddress::try_from(word_as_felt(to_address)?)?,
+            selector: EntryPointSelector(word_as_felt(selector)?),
+            nonce,
+            fee,
+        })
+    }
+}
+
+/// Parses a `LogMessageToL1` log.
+impl TryFrom<&EthereumLog> for MessageToL1 {
+    type Error = StarknetApiError;
+
+    fn try_from(log: &EthereumLog) -> Result<Self, Self::Error> {
+        let [topic, from_address, to_address] = log.topics[..] else {
+            return Err(invalid_log(format!(
+                "expected 3 topics in LogMessageToL1, found {}",
+                log.topics.len()
+            )));
+        };
+        check_topic(topic, *LOG_MESSAGE_TO_L1_TOPIC, "LogMessageToL1")?;
+        // The data is `(payload)`.
+        let payload = AbiData::new(&log.data)?.array(0)?;
+        Ok(Self {
+            from_address: ContractAddress::try_from(word_as_felt(from_address)?)?,
+            to_address: EthAddress::try_from(word_as_felt(to_address)?)?,
+            payload: L2ToL1Payload(payload),
+        })
+    }
+}
+
+fn invalid_log(reason: String) -> StarknetApiError {
+    StarknetApiError::InvalidEthereumLog(reason)
+}
+
+fn check_topic(topic: H256, expected: H256, event: &str) -> Result<(), StarknetApiError> {
+    if topic != expected {
+        return Err(invalid_log(format!("unexpected topic {topic:?} for {event}")));
+    }
+    Ok(())
+}
+
+fn word_as_felt(word: H256) -> Result<StarkFelt, StarknetApiError> {
+    StarkFelt::new(word.0)
+}
+
+// ABI-encoded data: a sequence of 32-byte words, where a dynamic array is encoded as the byte
+// offset of its length, followed by its elements.
+struct AbiData<'a> {
+    words: Vec<&'a [u8]>,
+}
+
+impl<'a> AbiData<'a> {
+    fn new(data: &'a [u8]) -> Result<Self, StarknetApiError> {
+        let words = data.chunks_exact(32);
+        if !words.remainder().is_empty() {
+            return Err(invalid_log(format!("data length {} is not a multiple of 32", data.len())));
+        }
+        Ok(Self { words: words.collect() })
+    }
+
+    fn word(&self, index: usize) -> Result<[u8; 32], StarknetApiError> {
+        let word = self
+            .words
+            .get(index)
+            .ok_or_else(|| invalid_log(format!("missing data word {index}")))?;
+        Ok((*word).try_into().expect("A data word should have 32 bytes."))
+    }
+
+    fn felt(&self, index: usize) -> Result<StarkFelt, StarknetApiError> {
+        StarkFelt::new(self.word(index)?)
+    }
+
+    fn usize(&self, index: usize) -> Result<usize, StarknetApiError> {
+        usize::try_from(self.felt(index)?)
+    }
+
+    fn u128(&self, index: usize) -> Result<u128, StarknetApiError> {
+        let word = self.word(index)?;
+        let (high, low) = word.split_at(16);
+        if high != [0u8; 16] {
+            return Err(StarknetApiError::OutOfRange { string: hex::encode(word) });
+        }
+        Ok(u128::from_be_bytes(
+            low.try_into().expect("The low half of a word should have 16 bytes."),
+        ))
+    }
+
+    // Returns the elements of the dynamic array whose offset is at `index`.
+    fn array(&self, index: usize) -> Result<Vec<StarkFelt>, StarknetApiError> {
+        let offset = self.usize(index)?;
+        let length_index = offset / 32;
+        if length_index * 32 != offset {
+            return Err(invalid_log(format!("array offset {offset} is not a multiple of 32")));
+        }
+        let length = self.usize(length_index)?;
+        (length_index + 1..).take(length).map(|element_index| self.felt(element_index)).collect()
+    }
+}
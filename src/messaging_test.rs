@@ -0,0 +1,123 @@
+use std::str::FromStr;
+
+use assert_matches::assert_matches;
+use primitive_types::H256;
+
+use crate::core::{ChainId, ContractAddress, EntryPointSelector, Nonce, PatriciaKey};
+use crate::hash::{StarkFelt, StarkHash};
+use crate::messaging::{
+    EthereumLog, L1ToL2Message, LOG_MESSAGE_TO_L1_TOPIC, LOG_MESSAGE_TO_L2_TOPIC,
+};
+use crate::transaction::{
+    Calldata, EthAddress, Fee, L1HandlerTransaction, L1ToL2Payload, L2ToL1Payload, MessageToL1,
+    MessageToL2, Transaction,
+};
+use crate::transaction_hash::{validate_transaction_hash, TransactionOptions};
+use crate::{calldata, patricia_key, stark_felt, StarknetApiError};
+
+fn word(value: u64) -> H256 {
+    H256::from_low_u64_be(value)
+}
+
+fn abi_data(words: &[u64]) -> Vec<u8> {
+    words.iter().flat_map(|value| word(*value).0).collect()
+}
+
+fn log_message_to_l2() -> EthereumLog {
+    EthereumLog {
+        topics: vec![*LOG_MESSAGE_TO_L2_TOPIC, word(0xabcdef), word(0x123), word(0x456)],
+        // The offset of the payload, the nonce, the fee and the payload.
+        data: abi_data(&[0x60, 7, 1000, 2, 1, 2]),
+    }
+}
+
+#[test]
+fn event_topics() {
+    assert_eq!(
+        *LOG_MESSAGE_TO_L1_TOPIC,
+        H256::from_str("0x4264ac208b5fde633ccdd42e0f12c3d6d443a4f3779bbf886925b94665b63a22")
+            .unwrap()
+    );
+    assert_eq!(
+        *LOG_MESSAGE_TO_L2_TOPIC,
+        H256::from_str("0xdb80dd488acf86d17c747445b0eabb5d57c541d3bd7b6b87af987858e5066b2b")
+            .unwrap()
+    );
+}
+
+#[test]
+fn parse_log_message_to_l2() {
+    let message = L1ToL2Message::try_from(&log_message_to_l2()).unwrap();
+    assert_eq!(
+        message,
+        L1ToL2Message {
+            message: MessageToL2 {
+                from_address: EthAddress::try_from(stark_felt!("0xabcdef")).unwrap(),
+                payload: L1ToL2Payload(vec![stark_felt!("0x1"), stark_felt!("0x2")]),
+            },
+            to_address: ContractAddress(patricia_key!("0x123")),
+            selector: EntryPointSelector(stark_felt!("0x456")),
+            nonce: Nonce(stark_felt!("0x7")),
+            fee: Fee(1000),
+        }
+    );
+    // Test vector generated with an independent Keccak-256 implementation.
+    assert_eq!(
+        message.hash(),
+        H256::from_str("0x7a7272ba2ddee2e70562f8bbdcd27f20162d278ff10c95e88569f0be2013761b")
+            .unwrap()
+    );
+}
+
+#[test]
+fn l1_handler_transaction_from_message() {
+    let chain_id = ChainId("SN_MAIN".to_string());
+    let message = L1ToL2Message::try_from(&log_message_to_l2()).unwrap();
+    let tx = L1HandlerTransaction::from_message(&message, &chain_id).unwrap();
+    assert_eq!(
+        tx.calldata,
+        calldata![stark_felt!("0xabcdef"), stark_felt!("0x1"), stark_felt!("0x2")]
+    );
+    assert_eq!(tx.message_hash().unwrap(), message.hash());
+    assert!(
+        validate_transaction_hash(
+            &Transaction::L1Handler(tx),
+            &chain_id,
+            &TransactionOptions::default()
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn parse_log_message_to_l1() {
+    let log = EthereumLog {
+        topics: vec![*LOG_MESSAGE_TO_L1_TOPIC, word(0x123), word(0xabcdef)],
+        data: abi_data(&[0x20, 2, 1, 2]),
+    };
+    assert_eq!(
+        MessageToL1::try_from(&log).unwrap(),
+        MessageToL1 {
+            from_address: ContractAddress(patricia_key!("0x123")),
+            to_address: EthAddress::try_from(stark_felt!("0xabcdef")).unwrap(),
+            payload: L2ToL1Payload(vec![stark_felt!("0x1"), stark_felt!("0x2")]),
+        }
+    );
+}
+
+#[test]
+fn invalid_logs() {
+    // A LogMessageToL2 log is not a LogMessageToL1 log.
+    let mut log = log_message_to_l2();
+    log.topics.pop();
+    assert_matches!(MessageToL1::try_from(&log), Err(StarknetApiError::InvalidEthereumLog(_)));
+
+    let mut log = log_message_to_l2();
+    log.data.pop();
+    assert_matches!(L1ToL2Message::try_from(&log), Err(StarknetApiError::InvalidEthereumLog(_)));
+
+    // The payload is longer than the data.
+    let mut log = log_message_to_l2();
+    log.data = abi_data(&[0x60, 7, 1000, 3, 1, 2]);
+    assert_matches!(L1ToL2Message::try_from(&log), Err(StarknetApiError::InvalidEthereumLog(_)));
+}
@@ -0,0 +1,156 @@
+//! The calldata of the `__execute__` entry point of an account, which encodes a list of calls.
+#[cfg(test)]
+#[path = "multicall_test.rs"]
+mod multicall_test;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::{ContractAddress, EntryPointSelector};
+use crate::hash::StarkFelt;
+use crate::transaction::Calldata;
+use crate::StarknetApiError;
+
+/// A call of an entry point of a contract, made by an account.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+pub struct Call {
+    pub to: ContractAddress,
+    pub selector: EntryPointSelector,
+    pub calldata: Vec<StarkFelt>,
+}
+
+/// The layout in which an account encodes its calls.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+pub enum MulticallLayout {
+    /// The layout of Cairo 0 accounts: the number of calls, `to`, `selector`, `data_offset` and
+    /// `data_len` of each call, and then the length and the elements of the concatenated calldata
+    /// of the calls.
+    Cairo0,
+    /// The layout of Cairo 1 accounts: the number of calls, and then `to`, `selector`, the length
+    /// of the calldata and the calldata of each call.
+    Cairo1,
+}
+
+/// Encodes calls as the calldata of `__execute__`.
+pub fn encode_calls(calls: &[Call], layout: MulticallLayout) -> Calldata {
+    let mut calldata = vec![StarkFelt::from(calls.len() as u64)];
+    match layout {
+        MulticallLayout::Cairo0 => {
+            let mut data_offset = 0;
+            for call in calls {
+                calldata.extend([
+                    *call.to.0.key(),
+                    call.selector.0,
+                    StarkFelt::from(data_offset as u64),
+                    StarkFelt::from(call.calldata.len() as u64),
+                ]);
+                data_offset += call.calldata.len();
+            }
+            calldata.push(StarkFelt::from(data_offset as u64));
+            for call in calls {
+                calldata.extend_from_slice(&call.calldata);
+            }
+        }
+        MulticallLayout::Cairo1 => {
+            for call in calls {
+                calldata.extend([
+                    *call.to.0.key(),
+                    call.selector.0,
+                    StarkFelt::from(call.calldata.len() as u64),
+                ]);
+                calldata.extend_from_slice(&call.calldata);
+            }
+        }
+    }
+    Calldata(calldata.into())
+}
+
+/// Decodes the calldata of `__execute__` into the calls it encodes. Fails if the calldata is not
+/// a valid encoding in the given layout.
+pub fn decode_calls(
+    calldata: &Calldata,
+    layout: MulticallLayout,
+) -> Result<Vec<Call>, StarknetApiError> {
+    let mut reader = CalldataReader { calldata: &calldata.0, position: 0 };
+    let n_calls = reader.read_usize()?;
+    let calls = match layout {
+        MulticallLayout::Cairo0 => {
+            let mut call_array = Vec::new();
+            for _ in 0..n_calls {
+                let to = ContractAddress::try_from(reader.read()?)?;
+                let selector = EntryPointSelector(reader.read()?);
+                let data_offset = reader.read_usize()?;
+                let data_len = reader.read_usize()?;
+                call_array.push((to, selector, data_offset, data_len));
+            }
+            let data = reader.read_slice()?;
+            call_array
+                .into_iter()
+                .map(|(to, selector, data_offset, data_len)| {
+                    let calldata = data_offset
+                        .checked_add(data_len)
+                        .and_then(|data_end| data.get(data_offset..data_end))
+                        .ok_or_else(|| {
+                            invalid_calldata(format!(
+                                "call data at offset {data_offset} with length {data_len} is out \
+                                 of range"
+                            ))
+                        })?;
+                    Ok(Call { to, selector, calldata: calldata.to_vec() })
+                })
+                .collect::<Result<_, StarknetApiError>>()?
+        }
+        MulticallLayout::Cairo1 => {
+            let mut calls = Vec::new();
+            for _ in 0..n_calls {
+                let to = ContractAddress::try_from(reader.read()?)?;
+                let selector = EntryPointSelector(reader.read()?);
+                let calldata = reader.read_slice()?.to_vec();
+                calls.push(Call { to, selector, calldata });
+            }
+            calls
+        }
+    };
+    if reader.position != calldata.0.len() {
+        return Err(invalid_calldata(format!(
+            "{} unexpected elements at the end",
+            calldata.0.len() - reader.position
+        )));
+    }
+    Ok(calls)
+}
+
+fn invalid_calldata(reason: String) -> StarknetApiError {
+    StarknetApiError::InvalidMulticallCalldata(reason)
+}
+
+struct CalldataReader<'a> {
+    calldata: &'a [StarkFelt],
+    position: usize,
+}
+
+impl<'a> CalldataReader<'a> {
+    fn read(&mut self) -> Result<StarkFelt, StarknetApiError> {
+        let felt = self
+            .calldata
+            .get(self.position)
+            .ok_or_else(|| invalid_calldata("unexpected end of calldata".to_string()))?;
+        self.position += 1;
+        Ok(*felt)
+    }
+
+    fn read_usize(&mut self) -> Result<usize, StarknetApiError> {
+        usize::try_from(self.read()?)
+    }
+
+    // Reads a length followed by that many elements.
+    fn read_slice(&mut self) -> Result<&'a [StarkFelt], StarknetApiError> {
+        let len = self.read_usize()?;
+        let slice = self
+            .position
+            .checked_add(len)
+            .and_then(|end| self.calldata.get(self.position..end))
+            .ok_or_else(|| invalid_calldata("unexpected end of calldata".to_string()))?;
+        self.position += len;
+        Ok(slice)
+    }
+}
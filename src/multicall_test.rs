@@ -0,0 +1,86 @@
+use assert_matches::assert_matches;
+
+use crate::core::{ContractAddress, EntryPointSelector, PatriciaKey};
+use crate::hash::{StarkFelt, StarkHash};
+use crate::multicall::{decode_calls, encode_calls, Call, MulticallLayout};
+use crate::transaction::Calldata;
+use crate::{calldata, patricia_key, stark_felt, StarknetApiError};
+
+fn calls() -> Vec<Call> {
+    vec![
+        Call {
+            to: ContractAddress(patricia_key!("0x10")),
+            selector: EntryPointSelector(stark_felt!("0x11")),
+            calldata: vec![stark_felt!("0x1"), stark_felt!("0x2")],
+        },
+        Call {
+            to: ContractAddress(patricia_key!("0x20")),
+            selector: EntryPointSelector(stark_felt!("0x21")),
+            calldata: vec![stark_felt!("0x3")],
+        },
+    ]
+}
+
+#[test]
+fn cairo0_layout() {
+    let expected = calldata![
+        stark_felt!("0x2"),
+        // The call array.
+        stark_felt!("0x10"),
+        stark_felt!("0x11"),
+        stark_felt!("0x0"),
+        stark_felt!("0x2"),
+        stark_felt!("0x20"),
+        stark_felt!("0x21"),
+        stark_felt!("0x2"),
+        stark_felt!("0x1"),
+        // The concatenated calldata.
+        stark_felt!("0x3"),
+        stark_felt!("0x1"),
+        stark_felt!("0x2"),
+        stark_felt!("0x3")
+    ];
+    assert_eq!(encode_calls(&calls(), MulticallLayout::Cairo0), expected);
+    assert_eq!(decode_calls(&expected, MulticallLayout::Cairo0).unwrap(), calls());
+}
+
+#[test]
+fn cairo1_layout() {
+    let expected = calldata![
+        stark_felt!("0x2"),
+        stark_felt!("0x10"),
+        stark_felt!("0x11"),
+        stark_felt!("0x2"),
+        stark_felt!("0x1"),
+        stark_felt!("0x2"),
+        stark_felt!("0x20"),
+        stark_felt!("0x21"),
+        stark_felt!("0x1"),
+        stark_felt!("0x3")
+    ];
+    assert_eq!(encode_calls(&calls(), MulticallLayout::Cairo1), expected);
+    assert_eq!(decode_calls(&expected, MulticallLayout::Cairo1).unwrap(), calls());
+}
+
+#[test]
+fn invalid_calldata() {
+    let encoded = encode_calls(&calls(), MulticallLayout::Cairo1);
+    // The Cairo 1 encoding is not a valid Cairo 0 encoding.
+    assert_matches!(
+        decode_calls(&encoded, MulticallLayout::Cairo0),
+        Err(StarknetApiError::InvalidMulticallCalldata(_))
+    );
+
+    let truncated = Calldata(encoded.0[..encoded.0.len() - 1].to_vec().into());
+    assert_matches!(
+        decode_calls(&truncated, MulticallLayout::Cairo1),
+        Err(StarknetApiError::InvalidMulticallCalldata(_))
+    );
+
+    let mut extended = encoded.0.to_vec();
+    extended.push(stark_felt!("0x0"));
+    assert_matches!(
+        decode_calls(&Calldata(extended.into()), MulticallLayout::Cairo1),
+        Err(StarknetApiError::InvalidMulticallCalldata(_))
+    );
+}
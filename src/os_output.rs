@@ -0,0 +1,360 @@
+//! The output of the Starknet OS, which is posted to L1 with the proof of a block.
+//!
+//! The output starts with a header whose fields depend on the [`OsOutputVersion`], then has the
+//! KZG commitments to the blobs of the block if its state diff is published in blobs, the messages
+//! to L1 and to L2, and the state diff if it is published in calldata. The state diff is encoded as
+//! for data availability, see [`OsOutputVersion::encode_state_diff`]. Full outputs, whose state
+//! diff also has the previous values and is never compressed, are not supported.
+#[cfg(test)]
+#[path = "os_output_test.rs"]
+mod os_output_test;
+
+use crate::block::{BlockHash, BlockNumber, StarknetVersion};
+use crate::block_hash::is_before;
+use crate::core::{ContractAddress, EntryPointSelector, GlobalRoot, Nonce};
+use crate::data_availability::{
+    compress, decode_state_diff, decompress, encode_state_diff, DataAvailabilityVersion,
+};
+use crate::hash::{StarkFelt, StarkHash};
+use crate::messaging::L1ToL2Message;
+use crate::state::ThinStateDiff;
+use crate::transaction::{EthAddress, Fee, L1ToL2Payload, L2ToL1Payload, MessageToL1, MessageToL2};
+use crate::StarknetApiError;
+
+/// The layout of the output of the Starknet OS, named after the Starknet version that introduced
+/// it.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum OsOutputVersion {
+    /// The header has the roots, the number and hash of the new block, the config hash and whether
+    /// the state diff is published in blobs.
+    V0_13_1,
+    /// The header also has the number and hash of the previous block, the hash of the OS program
+    /// and whether the output is full.
+    V0_13_2,
+    /// The header is as in [`OsOutputVersion::V0_13_2`]. The state diff is encoded with
+    /// [`DataAvailabilityVersion::V0_13_3`] and compressed with [`compress`].
+    #[default]
+    V0_13_3,
+}
+
+impl OsOutputVersion {
+    /// Returns the layout of the output of the OS of the given Starknet version. Versions before
+    /// v0.13.1 are not supported.
+    pub fn from_starknet_version(version: &StarknetVersion) -> Result<Self, StarknetApiError> {
+        if is_before(version, &[0, 13, 1]) {
+            return Err(invalid_output(&format!("unsupported Starknet version {}", version.0)));
+        }
+        if is_before(version, &[0, 13, 2]) {
+            return Ok(OsOutputVersion::V0_13_1);
+        }
+        if is_before(version, &[0, 13, 3]) {
+            return Ok(OsOutputVersion::V0_13_2);
+        }
+        Ok(OsOutputVersion::V0_13_3)
+    }
+
+    /// Encodes a state diff as the OS of this version publishes it, in the output or in blobs.
+    pub fn encode_state_diff(
+        &self,
+        state_diff: &ThinStateDiff,
+    ) -> Result<Vec<StarkFelt>, StarknetApiError> {
+        match self {
+            OsOutputVersion::V0_13_1 | OsOutputVersion::V0_13_2 => {
+                encode_state_diff(state_diff, DataAvailabilityVersion::V0_13_1)
+            }
+            OsOutputVersion::V0_13_3 => {
+                compress(&encode_state_diff(state_diff, DataAvailabilityVersion::V0_13_3)?)
+            }
+        }
+    }
+
+    /// Decodes a state diff that the OS of this version published, in the output or in blobs.
+    pub fn decode_state_diff(&self, data: &[StarkFelt]) -> Result<ThinStateDiff, StarknetApiError> {
+        match self {
+            OsOutputVersion::V0_13_1 | OsOutputVersion::V0_13_2 => {
+                decode_state_diff(data, DataAvailabilityVersion::V0_13_1)
+            }
+            OsOutputVersion::V0_13_3 => {
+                decode_state_diff(&decompress(data)?, DataAvailabilityVersion::V0_13_3)
+            }
+        }
+    }
+}
+
+/// The output of the Starknet OS for a block.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct StarknetOsOutput {
+    pub version: OsOutputVersion,
+    pub initial_root: GlobalRoot,
+    pub final_root: GlobalRoot,
+    /// Not in the output before [`OsOutputVersion::V0_13_2`].
+    pub prev_block_number: Option<BlockNumber>,
+    pub new_block_number: BlockNumber,
+    /// Not in the output before [`OsOutputVersion::V0_13_2`].
+    pub prev_block_hash: Option<BlockHash>,
+    pub new_block_hash: BlockHash,
+    /// Not in the output before [`OsOutputVersion::V0_13_2`].
+    pub os_program_hash: Option<StarkHash>,
+    pub starknet_os_config_hash: StarkHash,
+    pub messages_to_l1: Vec<MessageToL1>,
+    /// The messages from L1 that were consumed. Their fee is not part of the output and is 0.
+    pub messages_to_l2: Vec<L1ToL2Message>,
+    pub data_availability: OsDataAvailability,
+}
+
+/// How the state diff of a block is published for data availability.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum OsDataAvailability {
+    /// The state diff is in the output, as decoded by [`OsOutputVersion::decode_state_diff`]: all
+    /// class updates are deployed contracts and nonces that are 0 are dropped.
+    Calldata(Box<ThinStateDiff>),
+    /// The state diff is in blobs, and the output has the commitments to them.
+    Kzg(KzgCommitmentInfo),
+}
+
+/// The commitments to the blobs in which the state diff of a block is published. Each 384-bit
+/// number is split into its low and high 192 bits.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct KzgCommitmentInfo {
+    /// The point at which the polynomial of each blob is evaluated.
+    pub z: StarkFelt,
+    /// The KZG commitment to each blob.
+    pub kzg_commitments: Vec<(StarkFelt, StarkFelt)>,
+    /// The evaluation at `z` of the polynomial of each blob.
+    pub evals: Vec<(StarkFelt, StarkFelt)>,
+}
+
+impl StarknetOsOutput {
+    /// Parses the output of the Starknet OS with the given layout.
+    pub fn from_felts(
+        output: &[StarkFelt],
+        version: OsOutputVersion,
+    ) -> Result<Self, StarknetApiError> {
+        let mut reader = OutputReader { output, position: 0 };
+        let initial_root = GlobalRoot(reader.read()?);
+        let final_root = GlobalRoot(reader.read()?);
+        let (prev_block_number, new_block_number, prev_block_hash, new_block_hash, os_program_hash) =
+            match version {
+                OsOutputVersion::V0_13_1 => {
+                    (None, BlockNumber(reader.read_u64()?), None, BlockHash(reader.read()?), None)
+                }
+                OsOutputVersion::V0_13_2 | OsOutputVersion::V0_13_3 => (
+                    Some(BlockNumber(reader.read_u64()?)),
+                    BlockNumber(reader.read_u64()?),
+                    Some(BlockHash(reader.read()?)),
+                    BlockHash(reader.read()?),
+                    Some(reader.read()?),
+                ),
+            };
+        let starknet_os_config_hash = reader.read()?;
+        let use_kzg_da = reader.read_bool()?;
+        if version != OsOutputVersion::V0_13_1 && reader.read_bool()? {
+            return Err(invalid_output("full output is not supported"));
+        }
+
+        let kzg_commitment_info = if use_kzg_da {
+            let z = reader.read()?;
+            let n_blobs = reader.read_usize()?;
+            let kzg_commitments = reader.read_uint384_array(n_blobs)?;
+            let evals = reader.read_uint384_array(n_blobs)?;
+            Some(KzgCommitmentInfo { z, kzg_commitments, evals })
+        } else {
+            None
+        };
+
+        let messages_to_l1_end = reader.read_segment_end()?;
+        let mut messages_to_l1 = Vec::new();
+        while reader.position < messages_to_l1_end {
+            messages_to_l1.push(MessageToL1 {
+                from_address: ContractAddress::try_from(reader.read()?)?,
+                to_address: EthAddress::try_from(reader.read()?)?,
+                payload: L2ToL1Payload(reader.read_array()?),
+            });
+        }
+        reader.check_segment_end(messages_to_l1_end)?;
+
+        let messages_to_l2_end = reader.read_segment_end()?;
+        let mut messages_to_l2 = Vec::new();
+        while reader.position < messages_to_l2_end {
+            let from_address = EthAddress::try_from(reader.read()?)?;
+            let to_address = ContractAddress::try_from(reader.read()?)?;
+            let nonce = Nonce(reader.read()?);
+            let selector = EntryPointSelector(reader.read()?);
+            let payload = L1ToL2Payload(reader.read_array()?);
+            messages_to_l2.push(L1ToL2Message {
+                message: MessageToL2 { from_address, payload },
+                to_address,
+                selector,
+                nonce,
+                fee: Fee::default(),
+            });
+        }
+        reader.check_segment_end(messages_to_l2_end)?;
+
+        let data_availability = match kzg_commitment_info {
+            Some(kzg_commitment_info) => {
+                if reader.position != output.len() {
+                    return Err(invalid_output("unexpected elements at the end"));
+                }
+                OsDataAvailability::Kzg(kzg_commitment_info)
+            }
+            // The state diff takes the rest of the output.
+            None => OsDataAvailability::Calldata(Box::new(
+                version.decode_state_diff(&output[reader.position..])?,
+            )),
+        };
+        Ok(Self {
+            version,
+            initial_root,
+            final_root,
+            prev_block_number,
+            new_block_number,
+            prev_block_hash,
+            new_block_hash,
+            os_program_hash,
+            starknet_os_config_hash,
+            messages_to_l1,
+            messages_to_l2,
+            data_availability,
+        })
+    }
+
+    /// Encodes the output as the Starknet OS does, with the layout of its version. Fails if a
+    /// field of the header of the layout is missing, or if the state diff cannot be encoded.
+    pub fn to_felts(&self) -> Result<Vec<StarkFelt>, StarknetApiError> {
+        let use_kzg_da = matches!(self.data_availability, OsDataAvailability::Kzg(_));
+        let mut output = vec![self.initial_root.0, self.final_root.0];
+        match self.version {
+            OsOutputVersion::V0_13_1 => output.extend([
+                StarkFelt::from(self.new_block_number.0),
+                self.new_block_hash.0,
+                self.starknet_os_config_hash,
+                StarkFelt::from(u8::from(use_kzg_da)),
+            ]),
+            OsOutputVersion::V0_13_2 | OsOutputVersion::V0_13_3 => output.extend([
+                StarkFelt::from(required(self.prev_block_number, "prev_block_number")?.0),
+                StarkFelt::from(self.new_block_number.0),
+                required(self.prev_block_hash, "prev_block_hash")?.0,
+                self.new_block_hash.0,
+                required(self.os_program_hash, "os_program_hash")?,
+                self.starknet_os_config_hash,
+                StarkFelt::from(u8::from(use_kzg_da)),
+                // Not a full output.
+                StarkFelt::from(0_u8),
+            ]),
+        }
+
+        if let OsDataAvailability::Kzg(kzg_commitment_info) = &self.data_availability {
+            let n_blobs = kzg_commitment_info.kzg_commitments.len();
+            if kzg_commitment_info.evals.len() != n_blobs {
+                return Err(invalid_output("the number of evaluations differs from the blobs"));
+            }
+            output.extend([kzg_commitment_info.z, StarkFelt::from(n_blobs as u64)]);
+            for (low, high) in
+                kzg_commitment_info.kzg_commitments.iter().chain(&kzg_commitment_info.evals)
+            {
+                output.extend([*low, *high]);
+            }
+        }
+
+        let mut messages_to_l1 = Vec::new();
+        for message in &self.messages_to_l1 {
+            messages_to_l1.extend([*message.from_address.0.key(), message.to_address.into()]);
+            push_array(&mut messages_to_l1, &message.payload.0);
+        }
+        push_array(&mut output, &messages_to_l1);
+
+        let mut messages_to_l2 = Vec::new();
+        for message in &self.messages_to_l2 {
+            messages_to_l2.extend([
+                message.message.from_address.into(),
+                *message.to_address.0.key(),
+                message.nonce.0,
+                message.selector.0,
+            ]);
+            push_array(&mut messages_to_l2, &message.message.payload.0);
+        }
+        push_array(&mut output, &messages_to_l2);
+
+        if let OsDataAvailability::Calldata(state_diff) = &self.data_availability {
+            output.extend(self.version.encode_state_diff(state_diff)?);
+        }
+        Ok(output)
+    }
+}
+
+fn push_array(output: &mut Vec<StarkFelt>, array: &[StarkFelt]) {
+    output.push(StarkFelt::from(array.len() as u64));
+    output.extend_from_slice(array);
+}
+
+fn required<T>(field: Option<T>, name: &str) -> Result<T, StarknetApiError> {
+    field.ok_or_else(|| invalid_output(&format!("missing {name}")))
+}
+
+fn invalid_output(reason: &str) -> StarknetApiError {
+    StarknetApiError::InvalidOsOutput(reason.to_string())
+}
+
+struct OutputReader<'a> {
+    output: &'a [StarkFelt],
+    position: usize,
+}
+
+impl<'a> OutputReader<'a> {
+    fn read(&mut self) -> Result<StarkFelt, StarknetApiError> {
+        let felt = self
+            .output
+            .get(self.position)
+            .ok_or_else(|| invalid_output("unexpected end of output"))?;
+        self.position += 1;
+        Ok(*felt)
+    }
+
+    fn read_u64(&mut self) -> Result<u64, StarknetApiError> {
+        let felt = self.read()?;
+        let (high, low) = felt.bytes().split_at(24);
+        if high.iter().any(|byte| *byte != 0) {
+            return Err(StarknetApiError::OutOfRange { string: felt.to_string() });
+        }
+        Ok(u64::from_be_bytes(low.try_into().expect("The low bytes should be a u64.")))
+    }
+
+    fn read_usize(&mut self) -> Result<usize, StarknetApiError> {
+        usize::try_from(self.read()?)
+    }
+
+    fn read_bool(&mut self) -> Result<bool, StarknetApiError> {
+        match self.read_u64()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            value => Err(invalid_output(&format!("invalid flag {value}"))),
+        }
+    }
+
+    fn read_array(&mut self) -> Result<Vec<StarkFelt>, StarknetApiError> {
+        let len = self.read_usize()?;
+        (0..len).map(|_| self.read()).collect()
+    }
+
+    // Reads 384-bit numbers, each split into its low and high 192 bits.
+    fn read_uint384_array(
+        &mut self,
+        len: usize,
+    ) -> Result<Vec<(StarkFelt, StarkFelt)>, StarknetApiError> {
+        (0..len).map(|_| Ok((self.read()?, self.read()?))).collect()
+    }
+
+    // Reads the size of a segment and returns the position of its end.
+    fn read_segment_end(&mut self) -> Result<usize, StarknetApiError> {
+        let size = self.read_usize()?;
+        self.position.checked_add(size).ok_or_else(|| invalid_output("segment size overflow"))
+    }
+
+    fn check_segment_end(&self, segment_end: usize) -> Result<(), StarknetApiError> {
+        if self.position != segment_end {
+            return Err(invalid_output("a message exceeds its segment"));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,251 @@
+use assert_matches::assert_matches;
+use indexmap::indexmap;
+use primitive_types::H160;
+
+use crate::block::{BlockHash, BlockNumber, StarknetVersion};
+use crate::core::{
+    ClassHash, CompiledClassHash, ContractAddress, EntryPointSelector, GlobalRoot, Nonce,
+    PatriciaKey,
+};
+use crate::data_availability::{compress, encode_state_diff, DataAvailabilityVersion};
+use crate::hash::{StarkFelt, StarkHash};
+use crate::messaging::L1ToL2Message;
+use crate::os_output::{KzgCommitmentInfo, OsDataAvailability, OsOutputVersion, StarknetOsOutput};
+use crate::state::{StorageKey, ThinStateDiff};
+use crate::transaction::{EthAddress, Fee, L1ToL2Payload, L2ToL1Payload, MessageToL1, MessageToL2};
+use crate::{patricia_key, stark_felt, StarknetApiError};
+
+fn os_output() -> StarknetOsOutput {
+    let deployed = ContractAddress(patricia_key!("0x100"));
+    let updated = ContractAddress(patricia_key!("0x200"));
+    StarknetOsOutput {
+        version: OsOutputVersion::V0_13_2,
+        initial_root: GlobalRoot(stark_felt!("0x1")),
+        final_root: GlobalRoot(stark_felt!("0x2")),
+        prev_block_number: Some(BlockNumber(9)),
+        new_block_number: BlockNumber(10),
+        prev_block_hash: Some(BlockHash(stark_felt!("0x3"))),
+        new_block_hash: BlockHash(stark_felt!("0x4")),
+        os_program_hash: Some(stark_felt!("0x5")),
+        starknet_os_config_hash: stark_felt!("0x6"),
+        messages_to_l1: vec![MessageToL1 {
+            from_address: updated,
+            to_address: EthAddress(H160::from_low_u64_be(0xabc)),
+            payload: L2ToL1Payload(vec![stark_felt!("0x7"), stark_felt!("0x8")]),
+        }],
+        messages_to_l2: vec![L1ToL2Message {
+            message: MessageToL2 {
+                from_address: EthAddress(H160::from_low_u64_be(0xdef)),
+                payload: L1ToL2Payload(vec![stark_felt!("0x9")]),
+            },
+            to_address: deployed,
+            selector: EntryPointSelector(stark_felt!("0xa")),
+            nonce: Nonce(stark_felt!("0xb")),
+            fee: Fee::default(),
+        }],
+        data_availability: OsDataAvailability::Calldata(Box::new(ThinStateDiff {
+            deployed_contracts: indexmap! { deployed => ClassHash(stark_felt!("0x10")) },
+            storage_diffs: indexmap! {
+                updated => indexmap! {
+                    StorageKey(patricia_key!("0x1")) => stark_felt!("0x11"),
+                    StorageKey(patricia_key!("0x2")) => stark_felt!("0x12"),
+                },
+            },
+            declared_classes: indexmap! {
+                ClassHash(stark_felt!("0x20")) => CompiledClassHash(stark_felt!("0x21")),
+            },
+            deprecated_declared_classes: vec![],
+            nonces: indexmap! { updated => Nonce(stark_felt!("0x3")) },
+            replaced_classes: indexmap! {},
+        })),
+    }
+}
+
+fn state_diff(output: &StarknetOsOutput) -> &ThinStateDiff {
+    match &output.data_availability {
+        OsDataAvailability::Calldata(state_diff) => state_diff,
+        OsDataAvailability::Kzg(_) => panic!("The state diff is not in the output."),
+    }
+}
+
+fn state_diff_mut(output: &mut StarknetOsOutput) -> &mut ThinStateDiff {
+    match &mut output.data_availability {
+        OsDataAvailability::Calldata(state_diff) => state_diff,
+        OsDataAvailability::Kzg(_) => panic!("The state diff is not in the output."),
+    }
+}
+
+fn felts(values: &[&str]) -> Vec<StarkFelt> {
+    values.iter().map(|value| StarkFelt::try_from(*value).unwrap()).collect()
+}
+
+fn encoded_header() -> Vec<StarkFelt> {
+    // Without KZG data availability and full output.
+    felts(&["0x1", "0x2", "0x9", "0xa", "0x3", "0x4", "0x5", "0x6", "0x0", "0x0"])
+}
+
+fn encoded_messages() -> Vec<StarkFelt> {
+    [
+        // The messages to L1.
+        felts(&["0x5", "0x200", "0xabc", "0x2", "0x7", "0x8"]),
+        // The messages to L2.
+        felts(&["0x6", "0xdef", "0x100", "0xb", "0xa", "0x1", "0x9"]),
+    ]
+    .concat()
+}
+
+fn encoded_state_diff() -> Vec<StarkFelt> {
+    [
+        // A deployed contract and a contract with a nonce and storage updates.
+        felts(&["0x2", "0x100", "0x100000000000000000000000000000000", "0x10"]),
+        felts(&["0x200", "0x30000000000000002", "0x1", "0x11", "0x2", "0x12"]),
+        // The declared classes.
+        felts(&["0x1", "0x20", "0x21"]),
+    ]
+    .concat()
+}
+
+fn encoded_os_output() -> Vec<StarkFelt> {
+    [encoded_header(), encoded_messages(), encoded_state_diff()].concat()
+}
+
+#[test]
+fn encode_os_output() {
+    assert_eq!(os_output().to_felts().unwrap(), encoded_os_output());
+}
+
+#[test]
+fn parse_os_output() {
+    assert_eq!(
+        StarknetOsOutput::from_felts(&encoded_os_output(), OsOutputVersion::V0_13_2).unwrap(),
+        os_output()
+    );
+}
+
+#[test]
+fn os_output_v0_13_1() {
+    let output = StarknetOsOutput {
+        version: OsOutputVersion::V0_13_1,
+        prev_block_number: None,
+        prev_block_hash: None,
+        os_program_hash: None,
+        ..os_output()
+    };
+    // The roots, the new block number and hash, the config hash and no KZG data availability.
+    let header = felts(&["0x1", "0x2", "0xa", "0x4", "0x6", "0x0"]);
+    let encoded = [header, encoded_messages(), encoded_state_diff()].concat();
+    assert_eq!(output.to_felts().unwrap(), encoded);
+    assert_eq!(StarknetOsOutput::from_felts(&encoded, OsOutputVersion::V0_13_1).unwrap(), output);
+
+    // The header of the previous layout lacks fields.
+    let output = StarknetOsOutput { version: OsOutputVersion::V0_13_2, ..output };
+    assert_matches!(output.to_felts(), Err(StarknetApiError::InvalidOsOutput(_)));
+}
+
+#[test]
+fn os_output_with_kzg_data_availability() {
+    let output = StarknetOsOutput {
+        data_availability: OsDataAvailability::Kzg(KzgCommitmentInfo {
+            z: stark_felt!("0x7"),
+            kzg_commitments: vec![(stark_felt!("0xc1"), stark_felt!("0xc2"))],
+            evals: vec![(stark_felt!("0xe1"), stark_felt!("0xe2"))],
+        }),
+        ..os_output()
+    };
+    let mut header = encoded_header();
+    header[8] = stark_felt!("0x1");
+    // The evaluation point, the number of blobs, and the commitment and evaluation of each blob.
+    let kzg_commitment_info = felts(&["0x7", "0x1", "0xc1", "0xc2", "0xe1", "0xe2"]);
+    // The state diff is not in the output.
+    let encoded = [header, kzg_commitment_info, encoded_messages()].concat();
+    assert_eq!(output.to_felts().unwrap(), encoded);
+    assert_eq!(StarknetOsOutput::from_felts(&encoded, OsOutputVersion::V0_13_2).unwrap(), output);
+
+    let mut trailing = encoded;
+    trailing.push(StarkHash::default());
+    assert_matches!(
+        StarknetOsOutput::from_felts(&trailing, OsOutputVersion::V0_13_2),
+        Err(StarknetApiError::InvalidOsOutput(_))
+    );
+}
+
+#[test]
+fn os_output_v0_13_3() {
+    let output = StarknetOsOutput { version: OsOutputVersion::V0_13_3, ..os_output() };
+    let state_diff =
+        encode_state_diff(state_diff(&output), DataAvailabilityVersion::V0_13_3).unwrap();
+    // The state diff is compressed.
+    let encoded = [encoded_header(), encoded_messages(), compress(&state_diff).unwrap()].concat();
+    assert_eq!(output.to_felts().unwrap(), encoded);
+    assert_eq!(StarknetOsOutput::from_felts(&encoded, OsOutputVersion::V0_13_3).unwrap(), output);
+
+    // The state diff is not decompressed with the layout of v0.13.2.
+    assert!(StarknetOsOutput::from_felts(&encoded, OsOutputVersion::V0_13_2).is_err());
+}
+
+#[test]
+fn os_output_version_of_starknet_version() {
+    let version = |version: &str| {
+        OsOutputVersion::from_starknet_version(&StarknetVersion(version.to_string()))
+    };
+    assert_matches!(version("0.13.0"), Err(StarknetApiError::InvalidOsOutput(_)));
+    assert_eq!(version("0.13.1.1").unwrap(), OsOutputVersion::V0_13_1);
+    assert_eq!(version("0.13.2.1").unwrap(), OsOutputVersion::V0_13_2);
+    assert_eq!(version("0.13.3").unwrap(), OsOutputVersion::V0_13_3);
+    assert_eq!(version("0.13.4").unwrap(), OsOutputVersion::V0_13_3);
+}
+#[test]
+fn encoded_state_diff_is_sorted() {
+    let mut output = os_output();
+    let replaced = ContractAddress(patricia_key!("0x50"));
+    state_diff_mut(&mut output).replaced_classes.insert(replaced, ClassHash(stark_felt!("0x30")));
+    let updated = ContractAddress(patricia_key!("0x200"));
+    state_diff_mut(&mut output).storage_diffs[&updated] = indexmap! {
+        StorageKey(patricia_key!("0x2")) => stark_felt!("0x12"),
+        StorageKey(patricia_key!("0x1")) => stark_felt!("0x11"),
+    };
+
+    let parsed =
+        StarknetOsOutput::from_felts(&output.to_felts().unwrap(), OsOutputVersion::V0_13_2)
+            .unwrap();
+    // Replaced classes are parsed as deployed contracts.
+    assert_eq!(
+        state_diff(&parsed).deployed_contracts.clone().into_iter().collect::<Vec<_>>(),
+        vec![
+            (replaced, ClassHash(stark_felt!("0x30"))),
+            (ContractAddress(patricia_key!("0x100")), ClassHash(stark_felt!("0x10"))),
+        ]
+    );
+    assert_eq!(state_diff(&parsed).storage_diffs, state_diff(&os_output()).storage_diffs);
+}
+
+#[test]
+fn parse_invalid_os_output() {
+    let output = encoded_os_output();
+    assert_matches!(
+        StarknetOsOutput::from_felts(&output[..15], OsOutputVersion::V0_13_2),
+        Err(StarknetApiError::InvalidOsOutput(_))
+    );
+
+    let mut trailing = output.clone();
+    trailing.push(StarkHash::default());
+    assert_matches!(
+        StarknetOsOutput::from_felts(&trailing, OsOutputVersion::V0_13_2),
+        Err(StarknetApiError::InvalidDataAvailabilityEncoding(_))
+    );
+
+    let mut full_output = output.clone();
+    full_output[9] = stark_felt!("0x1");
+    assert_matches!(
+        StarknetOsOutput::from_felts(&full_output, OsOutputVersion::V0_13_2),
+        Err(StarknetApiError::InvalidOsOutput(_))
+    );
+
+    // A message to L1 whose payload exceeds the messages segment.
+    let mut long_payload = output;
+    long_payload[13] = stark_felt!("0x3");
+    assert_matches!(
+        StarknetOsOutput::from_felts(&long_payload, OsOutputVersion::V0_13_2),
+        Err(StarknetApiError::InvalidOsOutput(_))
+    );
+}
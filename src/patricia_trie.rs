@@ -0,0 +1,336 @@
+//! A binary Merkle-Patricia trie of height 251, as used for the Starknet
+//! [state commitment](https://docs.starknet.io/documentation/architecture_and_concepts/Network_Architecture/starknet-state/#merkle_patricia_trie).
+//!
+//! The nodes of the trie are kept in a [`TrieStorage`], keyed by their hash.
+#[cfg(test)]
+#[path = "patricia_trie_test.rs"]
+mod patricia_trie_test;
+
+use std::collections::{BTreeMap, HashMap};
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+use starknet_crypto::FieldElement;
+
+use crate::core::PatriciaKey;
+use crate::global_state::ContractState;
+use crate::hash::{HashFunction, StarkFelt, StarkHash};
+use crate::StarknetApiError;
+
+/// The height of the Patricia tries of the global state.
+pub const TRIE_HEIGHT: u8 = 251;
+
+/// The path of an edge node: the `length` least significant bits of `path`, from the most
+/// significant to the least significant bit.
+#[derive(
+    Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord,
+)]
+pub struct EdgePath {
+    #[serde(rename = "value")]
+    pub path: StarkFelt,
+    #[serde(rename = "len")]
+    pub length: u8,
+}
+
+/// An inner node of a Patricia trie. Serialized as a node of a `starknet_getProof` proof.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum TrieNode {
+    /// A node with two children.
+    Binary { left: StarkHash, right: StarkHash },
+    /// A node with a single child, which is reached by following the path of the edge.
+    Edge { child: StarkHash, path: EdgePath },
+}
+
+impl TrieNode {
+    /// Returns the hash of the node.
+    pub fn hash<H: HashFunction>(&self) -> StarkHash {
+        match self {
+            TrieNode::Binary { left, right } => H::hash(left, right),
+            TrieNode::Edge { child, path } => StarkFelt::from(
+                FieldElement::from(H::hash(child, &path.path)) + FieldElement::from(path.length),
+            ),
+        }
+    }
+}
+
+/// A storage of the nodes of Patricia tries, keyed by their hash, and of the contract states
+/// that are the leaves of contract tries, keyed by their hash.
+pub trait TrieStorage {
+    /// Returns the node with the given hash, if it exists.
+    fn get_node(&self, hash: &StarkHash) -> Option<TrieNode>;
+    /// Stores a node under its hash.
+    fn set_node(&mut self, hash: StarkHash, node: TrieNode);
+    /// Returns the contract state with the given hash, if it exists.
+    fn get_contract_state(&self, hash: &StarkHash) -> Option<ContractState>;
+    /// Stores a contract state under its hash.
+    fn set_contract_state(&mut self, hash: StarkHash, state: ContractState);
+}
+
+/// A [`TrieStorage`] that keeps the nodes and the contract states in memory.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct InMemoryTrieStorage {
+    nodes: HashMap<StarkHash, TrieNode>,
+    contract_states: HashMap<StarkHash, ContractState>,
+}
+
+impl InMemoryTrieStorage {
+    /// Returns the number of stored nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
+
+impl TrieStorage for InMemoryTrieStorage {
+    fn get_node(&self, hash: &StarkHash) -> Option<TrieNode> {
+        self.nodes.get(hash).copied()
+    }
+
+    fn set_node(&mut self, hash: StarkHash, node: TrieNode) {
+        self.nodes.insert(hash, node);
+    }
+
+    fn get_contract_state(&self, hash: &StarkHash) -> Option<ContractState> {
+        self.contract_states.get(hash).copied()
+    }
+
+    fn set_contract_state(&mut self, hash: StarkHash, state: ContractState) {
+        self.contract_states.insert(hash, state);
+    }
+}
+
+/// A Patricia trie of height [`TRIE_HEIGHT`], whose root is 0 when the trie is empty. Its nodes
+/// are hashed with `H`.
+///
+/// The trie reads its nodes through `S`, a reference to a [`TrieStorage`]. A shared reference is
+/// enough to read the trie, and a mutable reference is needed to update it.
+pub struct PatriciaTrie<S: Deref, H: HashFunction>
+where
+    S::Target: TrieStorage,
+{
+    storage: S,
+    root: StarkHash,
+    _hash_function: PhantomData<H>,
+}
+
+impl<S: Deref, H: HashFunction> PatriciaTrie<S, H>
+where
+    S::Target: TrieStorage,
+{
+    pub fn new(storage: S, root: StarkHash) -> Self {
+        Self { storage, root, _hash_function: PhantomData }
+    }
+
+    pub fn root(&self) -> StarkHash {
+        self.root
+    }
+
+    /// Returns the value of the leaf at `key`, which is 0 if the leaf does not exist.
+    pub fn get(&self, key: &PatriciaKey) -> Result<StarkFelt, StarknetApiError> {
+        let key = felt_as_u256(key.key());
+        let mut subtrie = self.root_subtrie();
+        for height in (1..=TRIE_HEIGHT).rev() {
+            let Some(current) = subtrie else {
+                break;
+            };
+            let (left, right) = self.split(current)?;
+            subtrie = if key.bit(usize::from(height - 1)) { right } else { left };
+        }
+        Ok(match subtrie {
+            Some(SubTrie::Stored(leaf)) => leaf,
+            _ => StarkFelt::from(0_u8),
+        })
+    }
+
+    /// Returns the nodes on the path from the root to the leaf at `key`. The path ends early at an
+    /// edge that diverges from the key, which proves that the leaf does not exist.
+    pub fn get_proof(&self, key: &PatriciaKey) -> Result<Vec<TrieNode>, StarknetApiError> {
+        let key = felt_as_u256(key.key());
+        let mut proof = Vec::new();
+        let mut hash = self.root;
+        let mut height = TRIE_HEIGHT;
+        while height > 0 && hash != StarkFelt::from(0_u8) {
+            let node =
+                self.storage.get_node(&hash).ok_or(StarknetApiError::MissingTrieNode { hash })?;
+            proof.push(node);
+            match node {
+                TrieNode::Binary { left, right } => {
+                    height -= 1;
+                    hash = if key.bit(usize::from(height)) { right } else { left };
+                }
+                TrieNode::Edge { child, path } => {
+                    height -= path.length;
+                    if (key >> height) & low_bits_mask(path.length) != felt_as_u256(&path.path) {
+                        break;
+                    }
+                    hash = child;
+                }
+            }
+        }
+        Ok(proof)
+    }
+
+    fn root_subtrie(&self) -> Option<SubTrie> {
+        (self.root != StarkFelt::from(0_u8)).then_some(SubTrie::Stored(self.root))
+    }
+
+    // Returns the left and right subtries of a subtrie whose height is positive.
+    fn split(
+        &self,
+        subtrie: SubTrie,
+    ) -> Result<(Option<SubTrie>, Option<SubTrie>), StarknetApiError> {
+        let (child, path, length) = match subtrie {
+            SubTrie::Stored(hash) => match self.storage.get_node(&hash) {
+                Some(TrieNode::Binary { left, right }) => {
+                    return Ok((Some(SubTrie::Stored(left)), Some(SubTrie::Stored(right))));
+                }
+                Some(TrieNode::Edge { child, path }) => {
+                    (child, felt_as_u256(&path.path), path.length)
+                }
+                None => return Err(StarknetApiError::MissingTrieNode { hash }),
+            },
+            SubTrie::Edge { child, path, length } => (child, path, length),
+        };
+        let rest_length = length - 1;
+        let rest = if rest_length == 0 {
+            SubTrie::Stored(child)
+        } else {
+            SubTrie::Edge { child, path: path & low_bits_mask(rest_length), length: rest_length }
+        };
+        Ok(if path.bit(usize::from(rest_length)) { (None, Some(rest)) } else { (Some(rest), None) })
+    }
+
+    // Loads a stored edge node, so that it can be merged into the edge above it.
+    fn as_edge(&self, subtrie: SubTrie, height: u8) -> Result<SubTrie, StarknetApiError> {
+        let SubTrie::Stored(hash) = subtrie else {
+            return Ok(subtrie);
+        };
+        if height == 0 {
+            return Ok(subtrie);
+        }
+        match self.storage.get_node(&hash) {
+            Some(TrieNode::Edge { child, path }) => {
+                Ok(SubTrie::Edge { child, path: felt_as_u256(&path.path), length: path.length })
+            }
+            Some(TrieNode::Binary { .. }) => Ok(subtrie),
+            None => Err(StarknetApiError::MissingTrieNode { hash }),
+        }
+    }
+}
+
+impl<S: DerefMut, H: HashFunction> PatriciaTrie<S, H>
+where
+    S::Target: TrieStorage,
+{
+    /// Sets the values of the given leaves and returns the new root. A leaf whose value is 0 is
+    /// removed from the trie. If a key appears more than once, its last value is used.
+    pub fn update(
+        &mut self,
+        leaves: impl IntoIterator<Item = (PatriciaKey, StarkFelt)>,
+    ) -> Result<StarkHash, StarknetApiError> {
+        let sorted_leaves = leaves
+            .into_iter()
+            .map(|(key, value)| (felt_as_u256(key.key()), value))
+            .collect::<BTreeMap<_, _>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        let root = self.root_subtrie();
+        self.root = match self.update_subtrie(root, TRIE_HEIGHT, &sorted_leaves)? {
+            Some(subtrie) => self.store(subtrie),
+            None => StarkFelt::from(0_u8),
+        };
+        Ok(self.root)
+    }
+
+    // Leaves must be sorted by their key, and have the same bits above `height`.
+    fn update_subtrie(
+        &mut self,
+        subtrie: Option<SubTrie>,
+        height: u8,
+        leaves: &[(U256, StarkFelt)],
+    ) -> Result<Option<SubTrie>, StarknetApiError> {
+        let Some((_, last_value)) = leaves.last() else {
+            return Ok(subtrie);
+        };
+        if height == 0 {
+            return Ok(
+                (*last_value != StarkFelt::from(0_u8)).then_some(SubTrie::Stored(*last_value))
+            );
+        }
+        let (left, right) = match subtrie {
+            Some(subtrie) => self.split(subtrie)?,
+            None => (None, None),
+        };
+        let bit = usize::from(height - 1);
+        let split = leaves.partition_point(|(key, _)| !key.bit(bit));
+        let (left_leaves, right_leaves) = leaves.split_at(split);
+        let left = self.update_subtrie(left, height - 1, left_leaves)?;
+        let right = self.update_subtrie(right, height - 1, right_leaves)?;
+        Ok(match (left, right) {
+            (Some(left), Some(right)) => {
+                let node = TrieNode::Binary { left: self.store(left), right: self.store(right) };
+                let hash = node.hash::<H>();
+                self.storage.set_node(hash, node);
+                Some(SubTrie::Stored(hash))
+            }
+            (Some(left), None) => Some(self.as_edge(left, height - 1)?.extend(false)),
+            (None, Some(right)) => Some(self.as_edge(right, height - 1)?.extend(true)),
+            (None, None) => None,
+        })
+    }
+
+    // Stores the top node of the subtrie, if needed, and returns its hash.
+    fn store(&mut self, subtrie: SubTrie) -> StarkHash {
+        match subtrie {
+            SubTrie::Stored(hash) => hash,
+            SubTrie::Edge { child, path, length } => {
+                let node =
+                    TrieNode::Edge { child, path: EdgePath { path: u256_as_felt(path), length } };
+                let hash = node.hash::<H>();
+                self.storage.set_node(hash, node);
+                hash
+            }
+        }
+    }
+}
+
+// A non-empty subtrie, during a traversal of the trie.
+#[derive(Debug, Copy, Clone)]
+enum SubTrie {
+    // A stored node, or a leaf at height 0.
+    Stored(StarkHash),
+    // An edge which may not be stored, e.g. the lower part of a stored edge.
+    Edge { child: StarkHash, path: U256, length: u8 },
+}
+
+impl SubTrie {
+    // Returns the subtrie one level up, reached from its parent through the given bit.
+    fn extend(self, bit: bool) -> SubTrie {
+        let (child, path, length) = match self {
+            SubTrie::Stored(hash) => (hash, U256::zero(), 0),
+            SubTrie::Edge { child, path, length } => (child, path, length),
+        };
+        let path = if bit { path | (U256::one() << length) } else { path };
+        SubTrie::Edge { child, path, length: length + 1 }
+    }
+}
+
+pub(crate) fn low_bits_mask(n_bits: u8) -> U256 {
+    (U256::one() << n_bits) - 1
+}
+
+pub(crate) fn felt_as_u256(felt: &StarkFelt) -> U256 {
+    U256::from_big_endian(felt.bytes())
+}
+
+pub(crate) fn u256_as_felt(value: U256) -> StarkFelt {
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+    StarkFelt::new(bytes).expect("A trie path should fit in a StarkFelt.")
+}
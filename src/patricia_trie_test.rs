@@ -0,0 +1,95 @@
+use assert_matches::assert_matches;
+use starknet_crypto::FieldElement;
+
+use crate::core::PatriciaKey;
+use crate::hash::{pedersen_hash, Pedersen, StarkFelt, StarkHash};
+use crate::patricia_trie::{InMemoryTrieStorage, PatriciaTrie};
+use crate::{patricia_key, stark_felt, StarknetApiError};
+
+fn edge_hash(child: StarkHash, path: StarkFelt, length: u8) -> StarkHash {
+    StarkFelt::from(FieldElement::from(pedersen_hash(&child, &path)) + FieldElement::from(length))
+}
+
+#[test]
+fn single_leaf() {
+    let mut storage = InMemoryTrieStorage::default();
+    let mut trie = PatriciaTrie::<_, Pedersen>::new(&mut storage, StarkHash::default());
+    let root = trie.update([(patricia_key!("0x123"), stark_felt!("0x5"))]).unwrap();
+
+    assert_eq!(root, edge_hash(stark_felt!("0x5"), stark_felt!("0x123"), 251));
+    assert_eq!(trie.get(&patricia_key!("0x123")).unwrap(), stark_felt!("0x5"));
+    assert_eq!(trie.get(&patricia_key!("0x124")).unwrap(), stark_felt!("0x0"));
+}
+
+#[test]
+fn leaves_split_at_the_root() {
+    let mut storage = InMemoryTrieStorage::default();
+    let mut trie = PatriciaTrie::<_, Pedersen>::new(&mut storage, StarkHash::default());
+    // 2**250 is the smallest key whose most significant bit is set.
+    let right_key =
+        patricia_key!("0x400000000000000000000000000000000000000000000000000000000000000");
+    let root = trie
+        .update([(patricia_key!("0x1"), stark_felt!("0x7")), (right_key, stark_felt!("0x8"))])
+        .unwrap();
+
+    let expected = pedersen_hash(
+        &edge_hash(stark_felt!("0x7"), stark_felt!("0x1"), 250),
+        &edge_hash(stark_felt!("0x8"), stark_felt!("0x0"), 250),
+    );
+    assert_eq!(root, expected);
+    assert_eq!(trie.get(&right_key).unwrap(), stark_felt!("0x8"));
+}
+
+#[test]
+fn incremental_updates() {
+    let leaves = [
+        (patricia_key!("0x1"), stark_felt!("0x11")),
+        (patricia_key!("0x2"), stark_felt!("0x12")),
+        (patricia_key!("0x3"), stark_felt!("0x13")),
+        (patricia_key!("0x100"), stark_felt!("0x14")),
+        (patricia_key!("0x7ff"), stark_felt!("0x15")),
+    ];
+    let mut storage = InMemoryTrieStorage::default();
+    let mut batch_trie = PatriciaTrie::<_, Pedersen>::new(&mut storage, StarkHash::default());
+    let batch_root = batch_trie.update(leaves).unwrap();
+
+    let mut storage = InMemoryTrieStorage::default();
+    let mut trie = PatriciaTrie::<_, Pedersen>::new(&mut storage, StarkHash::default());
+    let mut roots = vec![trie.root()];
+    for leaf in leaves.iter().rev() {
+        roots.push(trie.update([*leaf]).unwrap());
+    }
+    assert_eq!(trie.root(), batch_root);
+    for (key, value) in leaves {
+        assert_eq!(trie.get(&key).unwrap(), value);
+    }
+
+    // Removing the leaves in reverse order goes back through the same roots.
+    roots.pop();
+    for (key, _) in leaves {
+        assert_eq!(trie.update([(key, StarkFelt::default())]).unwrap(), roots.pop().unwrap());
+    }
+    assert_eq!(trie.root(), StarkHash::default());
+}
+
+#[test]
+fn last_value_of_a_key_wins() {
+    let mut storage = InMemoryTrieStorage::default();
+    let mut trie = PatriciaTrie::<_, Pedersen>::new(&mut storage, StarkHash::default());
+    trie.update([
+        (patricia_key!("0x1"), stark_felt!("0x1")),
+        (patricia_key!("0x1"), stark_felt!("0x2")),
+    ])
+    .unwrap();
+    assert_eq!(trie.get(&patricia_key!("0x1")).unwrap(), stark_felt!("0x2"));
+}
+
+#[test]
+fn missing_node() {
+    let mut storage = InMemoryTrieStorage::default();
+    let trie = PatriciaTrie::<_, Pedersen>::new(&mut storage, stark_felt!("0x1234"));
+    assert_matches!(
+        trie.get(&patricia_key!("0x1")),
+        Err(StarknetApiError::MissingTrieNode { hash }) if hash == stark_felt!("0x1234")
+    );
+}
@@ -129,6 +129,38 @@ pub fn hex_str_from_bytes<const N: usize, const PREFIXED: bool>(bytes: [u8; N])
     if PREFIXED { format!("0x{hex_str}") } else { hex_str.to_string() }
 }
 
+/// Serializes a u64 as a prefixed hex string.
+pub fn serialize_u64_as_hex<S>(value: &u64, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    BytesAsHex::<8, true>(value.to_be_bytes()).serialize(serializer)
+}
+
+/// Deserializes a u64 from a prefixed hex string.
+pub fn deserialize_u64_from_hex<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(u64::from_be_bytes(PrefixedBytesAsHex::<8_usize>::deserialize(deserializer)?.0))
+}
+
+/// Serializes a u128 as a prefixed hex string.
+pub fn serialize_u128_as_hex<S>(value: &u128, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    BytesAsHex::<16, true>(value.to_be_bytes()).serialize(serializer)
+}
+
+/// Deserializes a u128 from a prefixed hex string.
+pub fn deserialize_u128_from_hex<'de, D>(deserializer: D) -> Result<u128, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(u128::from_be_bytes(PrefixedBytesAsHex::<16_usize>::deserialize(deserializer)?.0))
+}
+
 pub fn deserialize_optional_contract_class_abi_entry_vector<'de, D>(
     deserializer: D,
 ) -> Result<Option<Vec<ContractClassAbiEntry>>, D::Error>
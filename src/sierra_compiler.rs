@@ -0,0 +1,119 @@
+//! Compilation of a Sierra [ContractClass](`crate::state::ContractClass`) into a
+//! [CompiledContractClass](`crate::compiled_class::CompiledContractClass`), with the
+//! `cairo-lang-starknet` compiler.
+#[cfg(test)]
+#[path = "sierra_compiler_test.rs"]
+mod sierra_compiler_test;
+
+use std::fmt::{self, Display, Formatter};
+
+use cairo_lang_starknet::allowed_libfuncs::{AllowedLibfuncsError, ListSelector};
+use cairo_lang_starknet::casm_contract_class::{CasmContractClass, StarknetSierraCompilationError};
+use cairo_lang_starknet::compiler_version::current_sierra_version_id;
+use cairo_lang_starknet::contract_class::ContractClass as CairoLangContractClass;
+use serde_json::json;
+
+use crate::compiled_class::CompiledContractClass;
+use crate::state::{ContractClass, EntryPointType};
+use crate::StarknetApiError;
+
+/// The oldest Sierra version that can be compiled.
+pub const MIN_SIERRA_VERSION: SierraVersion = SierraVersion { major: 1, minor: 0, patch: 0 };
+
+/// Returns the newest Sierra version that the `cairo-lang-starknet` compiler supports. Programs
+/// with the same major and minor versions can be compiled, whatever their patch version.
+pub fn max_sierra_version() -> SierraVersion {
+    let version = current_sierra_version_id();
+    SierraVersion { major: version.major, minor: version.minor, patch: version.patch }
+}
+
+/// The version of a Sierra program, which is encoded in its first three felts.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct SierraVersion {
+    pub major: usize,
+    pub minor: usize,
+    pub patch: usize,
+}
+
+impl SierraVersion {
+    /// Returns the version of a Sierra program. Programs from before the version was encoded
+    /// in the program have version 0.1.0.
+    pub fn from_sierra_program(sierra_program: &[crate::hash::StarkFelt]) -> Self {
+        let version = sierra_program
+            .get(..3)
+            .map(|felts| felts.iter().map(|felt| usize::try_from(*felt)).collect::<Vec<_>>());
+        match version.as_deref() {
+            Some([Ok(major), Ok(minor), Ok(patch)]) => {
+                Self { major: *major, minor: *minor, patch: *patch }
+            }
+            _ => Self { major: 0, minor: 1, patch: 0 },
+        }
+    }
+}
+
+impl Display for SierraVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// The error type returned by [`compile_contract_class`].
+#[derive(thiserror::Error, Debug)]
+pub enum SierraCompilationError {
+    /// The Sierra version of the class is not supported by the compiler.
+    #[error(
+        "Unsupported Sierra version {version}; supported versions are from {MIN_SIERRA_VERSION} \
+         to {max_version}."
+    )]
+    UnsupportedSierraVersion { version: SierraVersion, max_version: SierraVersion },
+    /// The class uses a libfunc that is not in the allowed libfuncs list, or the list is invalid.
+    #[error(transparent)]
+    AllowedLibfuncs(#[from] AllowedLibfuncsError),
+    /// The compilation of the class failed.
+    #[error(transparent)]
+    Compilation(#[from] StarknetSierraCompilationError),
+    /// The class or its compiled class cannot be converted between the types of this crate and
+    /// of the compiler.
+    #[error(transparent)]
+    StarknetApi(#[from] StarknetApiError),
+}
+
+/// Compiles a Sierra contract class, after checking that its Sierra version is supported and that
+/// it uses only libfuncs from the given allowed libfuncs list.
+pub fn compile_contract_class(
+    class: &ContractClass,
+    allowed_libfuncs: ListSelector,
+) -> Result<CompiledContractClass, SierraCompilationError> {
+    let version = SierraVersion::from_sierra_program(&class.sierra_program);
+    let max_version = max_sierra_version();
+    if version < MIN_SIERRA_VERSION
+        || (version.major, version.minor) > (max_version.major, max_version.minor)
+    {
+        return Err(SierraCompilationError::UnsupportedSierraVersion { version, max_version });
+    }
+    let cairo_lang_class = as_cairo_lang_contract_class(class)?;
+    cairo_lang_class.validate_version_compatible(allowed_libfuncs)?;
+    let casm_class = CasmContractClass::from_contract_class(cairo_lang_class, false)?;
+    Ok(casm_class.try_into()?)
+}
+
+// The ABI and the debug info of the class are not needed for the compilation.
+fn as_cairo_lang_contract_class(
+    class: &ContractClass,
+) -> Result<CairoLangContractClass, StarknetApiError> {
+    let entry_points = |entry_point_type| {
+        class.entry_point_by_type.get(&entry_point_type).cloned().unwrap_or_default()
+    };
+    serde_json::from_value(json!({
+        "sierra_program": class.sierra_program,
+        "sierra_program_debug_info": null,
+        "contract_class_version": "0.1.0",
+        "entry_points_by_type": {
+            "EXTERNAL": entry_points(EntryPointType::External),
+            "L1_HANDLER": entry_points(EntryPointType::L1Handler),
+            "CONSTRUCTOR": entry_points(EntryPointType::Constructor),
+        },
+        "abi": null,
+    }))
+    .map_err(|err| StarknetApiError::InvalidContractClass(err.to_string()))
+}
@@ -0,0 +1,72 @@
+use assert_matches::assert_matches;
+use cairo_lang_starknet::allowed_libfuncs::{AllowedLibfuncsError, ListSelector};
+
+use crate::hash::StarkFelt;
+use crate::sierra_compiler::{
+    compile_contract_class, max_sierra_version, SierraCompilationError, SierraVersion,
+};
+use crate::stark_felt;
+use crate::state::ContractClass;
+
+fn contract_class(version: [u8; 3]) -> ContractClass {
+    let mut sierra_program = version.map(StarkFelt::from).to_vec();
+    sierra_program.extend([stark_felt!("0x2"), stark_felt!("0x0"), stark_felt!("0x0")]);
+    ContractClass { sierra_program, ..Default::default() }
+}
+
+#[test]
+fn sierra_version_from_program() {
+    assert_eq!(
+        SierraVersion::from_sierra_program(&contract_class([1, 3, 0]).sierra_program),
+        SierraVersion { major: 1, minor: 3, patch: 0 }
+    );
+    // The first felt of a program from before the version was encoded is a large hash.
+    let legacy_program =
+        [stark_felt!("0xa0c1bb1f10d7b43e5f58c01d9caad37d6d3c1a7ba97de38ee4c3c7a26")];
+    assert_eq!(
+        SierraVersion::from_sierra_program(&legacy_program),
+        SierraVersion { major: 0, minor: 1, patch: 0 }
+    );
+    assert_eq!(SierraVersion { major: 1, minor: 3, patch: 0 }.to_string(), "1.3.0");
+}
+
+#[test]
+fn unsupported_sierra_version() {
+    let max_version = max_sierra_version();
+    let major = u8::try_from(max_version.major).unwrap();
+    let minor = u8::try_from(max_version.minor).unwrap();
+    for version in [[0, 1, 0], [major, minor + 1, 0], [major + 1, 0, 0]] {
+        let err =
+            compile_contract_class(&contract_class(version), ListSelector::default()).unwrap_err();
+        assert_matches!(
+            err,
+            SierraCompilationError::UnsupportedSierraVersion {
+                version: SierraVersion { major, minor, .. },
+                ..
+            } if [major, minor] == [usize::from(version[0]), usize::from(version[1])]
+        );
+    }
+
+    // The patch version is not checked.
+    let err = compile_contract_class(
+        &contract_class([major, minor, 9]),
+        ListSelector::ListName("no_such_list".to_string()),
+    )
+    .unwrap_err();
+    assert_matches!(err, SierraCompilationError::AllowedLibfuncs(_));
+}
+
+#[test]
+fn unknown_allowed_libfuncs_list() {
+    let err = compile_contract_class(
+        &contract_class([1, 3, 0]),
+        ListSelector::ListName("no_such_list".to_string()),
+    )
+    .unwrap_err();
+    assert_matches!(
+        err,
+        SierraCompilationError::AllowedLibfuncs(
+            AllowedLibfuncsError::UnexpectedAllowedLibfuncsList { .. }
+        )
+    );
+}
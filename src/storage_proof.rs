@@ -0,0 +1,121 @@
+//! Merkle proofs of the global state, in the format of the `starknet_getProof` JSON-RPC method.
+#[cfg(test)]
+#[path = "storage_proof_test.rs"]
+mod storage_proof_test;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::{ClassHash, ContractAddress, GlobalRoot, Nonce, PatriciaKey};
+use crate::global_state::{calculate_contract_state_hash, calculate_global_root};
+use crate::hash::{HashFunction, Pedersen, StarkFelt, StarkHash};
+use crate::patricia_trie::{felt_as_u256, low_bits_mask, TrieNode, TRIE_HEIGHT};
+use crate::state::StorageKey;
+
+/// The nodes on the path from the root of a Patricia trie to a leaf, starting at the root.
+pub type MerkleProof = Vec<TrieNode>;
+
+/// A proof of the state of a contract and of some of its storage values.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+pub struct StorageProof {
+    /// The root of the class trie.
+    pub class_commitment: StarkHash,
+    /// The root of the contract trie.
+    pub state_commitment: StarkHash,
+    /// A proof of the leaf of the contract in the contract trie.
+    pub contract_proof: MerkleProof,
+    /// The state of the contract, or `None` if the contract is not deployed.
+    pub contract_data: Option<ContractData>,
+}
+
+/// The state of a contract and proofs of some of its storage values.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+pub struct ContractData {
+    pub class_hash: ClassHash,
+    pub nonce: Nonce,
+    /// The root of the storage trie of the contract.
+    pub root: StarkHash,
+    pub contract_state_hash_version: StarkFelt,
+    /// Proofs of storage values, each against the root of the storage trie.
+    pub storage_proofs: Vec<MerkleProof>,
+}
+
+/// Returns true if the proof shows that the storage value of the contract at `key` is `value`,
+/// in the state whose global root is `global_root`. A value of 0 is proven by showing that the
+/// contract or the storage key does not exist.
+pub fn verify_storage_proof(
+    proof: &StorageProof,
+    global_root: &GlobalRoot,
+    address: &ContractAddress,
+    key: &StorageKey,
+    value: &StarkFelt,
+) -> bool {
+    if calculate_global_root(proof.state_commitment, proof.class_commitment) != *global_root {
+        return false;
+    }
+    let contract_leaf =
+        verify_merkle_proof::<Pedersen>(&proof.state_commitment, &address.0, &proof.contract_proof);
+    let Some(contract_leaf) = contract_leaf else {
+        return false;
+    };
+    let Some(contract_data) = &proof.contract_data else {
+        return contract_leaf == StarkFelt::from(0_u8) && *value == StarkFelt::from(0_u8);
+    };
+    if contract_data.contract_state_hash_version != StarkFelt::from(0_u8)
+        || contract_leaf
+            != calculate_contract_state_hash(
+                &contract_data.class_hash,
+                &contract_data.root,
+                &contract_data.nonce,
+            )
+    {
+        return false;
+    }
+    // A proof that does not match the path of the key fails to verify, so any proof that
+    // verifies is a proof of the key.
+    contract_data.storage_proofs.iter().any(|storage_proof| {
+        verify_merkle_proof::<Pedersen>(&contract_data.root, &key.0, storage_proof) == Some(*value)
+    })
+}
+
+/// Returns the value of the leaf at `key` that the proof shows, which is 0 if the proof shows that
+/// the leaf does not exist, or `None` if the proof is invalid.
+pub fn verify_merkle_proof<H: HashFunction>(
+    root: &StarkHash,
+    key: &PatriciaKey,
+    proof: &[TrieNode],
+) -> Option<StarkFelt> {
+    if *root == StarkFelt::from(0_u8) {
+        // The trie is empty.
+        return proof.is_empty().then_some(StarkFelt::from(0_u8));
+    }
+    let key = felt_as_u256(key.key());
+    let mut expected_hash = *root;
+    let mut height = TRIE_HEIGHT;
+    for (index, node) in proof.iter().enumerate() {
+        if height == 0 || node.hash::<H>() != expected_hash {
+            return None;
+        }
+        match node {
+            TrieNode::Binary { left, right } => {
+                height -= 1;
+                expected_hash = if key.bit(usize::from(height)) { *right } else { *left };
+            }
+            TrieNode::Edge { child, path } => {
+                let path_bits = felt_as_u256(&path.path);
+                if path.length == 0
+                    || path.length > height
+                    || path_bits > low_bits_mask(path.length)
+                {
+                    return None;
+                }
+                height -= path.length;
+                if (key >> height) & low_bits_mask(path.length) != path_bits {
+                    // The edge leaves the path of the key, so the leaf does not exist.
+                    return (index + 1 == proof.len()).then_some(StarkFelt::from(0_u8));
+                }
+                expected_hash = *child;
+            }
+        }
+    }
+    (height == 0).then_some(expected_hash)
+}
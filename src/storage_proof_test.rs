@@ -0,0 +1,197 @@
+use indexmap::indexmap;
+use serde_json::json;
+
+use crate::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce, PatriciaKey};
+use crate::global_state::GlobalStateTrie;
+use crate::hash::{Pedersen, StarkFelt, StarkHash};
+use crate::patricia_trie::{EdgePath, InMemoryTrieStorage, TrieNode};
+use crate::state::{StorageKey, ThinStateDiff};
+use crate::storage_proof::{verify_merkle_proof, verify_storage_proof};
+use crate::{patricia_key, stark_felt};
+
+fn address(key: &str) -> ContractAddress {
+    ContractAddress(PatriciaKey::try_from(StarkHash::try_from(key).unwrap()).unwrap())
+}
+
+fn storage_key(key: &str) -> StorageKey {
+    StorageKey(PatriciaKey::try_from(StarkHash::try_from(key).unwrap()).unwrap())
+}
+
+fn global_state() -> GlobalStateTrie<InMemoryTrieStorage> {
+    let mut state = GlobalStateTrie::new(InMemoryTrieStorage::default());
+    state
+        .apply_thin_state_diff(&ThinStateDiff {
+            deployed_contracts: indexmap! {
+                address("0x100") => ClassHash(stark_felt!("0x10")),
+                address("0x200") => ClassHash(stark_felt!("0x10")),
+            },
+            storage_diffs: indexmap! {
+                address("0x100") => indexmap! {
+                    storage_key("0x5") => stark_felt!("0x55"),
+                    storage_key("0x6") => stark_felt!("0x66"),
+                },
+            },
+            declared_classes: indexmap! {
+                ClassHash(stark_felt!("0x20")) => CompiledClassHash(stark_felt!("0x21")),
+            },
+            deprecated_declared_classes: vec![],
+            nonces: indexmap! { address("0x100") => Nonce(stark_felt!("0x1")) },
+            replaced_classes: indexmap! {},
+        })
+        .unwrap();
+    state
+}
+
+#[test]
+fn storage_value() {
+    let state = global_state();
+    let global_root = state.roots().global_root;
+    let proof = state.get_proof(&address("0x100"), &[storage_key("0x5")]).unwrap();
+
+    assert!(verify_storage_proof(
+        &proof,
+        &global_root,
+        &address("0x100"),
+        &storage_key("0x5"),
+        &stark_felt!("0x55")
+    ));
+    assert!(!verify_storage_proof(
+        &proof,
+        &global_root,
+        &address("0x100"),
+        &storage_key("0x5"),
+        &stark_felt!("0x56")
+    ));
+    // The proof of a key does not prove the value of another key.
+    assert!(!verify_storage_proof(
+        &proof,
+        &global_root,
+        &address("0x100"),
+        &storage_key("0x6"),
+        &stark_felt!("0x55")
+    ));
+    let mut wrong_root = global_root;
+    wrong_root.0 = stark_felt!("0x1234");
+    assert!(!verify_storage_proof(
+        &proof,
+        &wrong_root,
+        &address("0x100"),
+        &storage_key("0x5"),
+        &stark_felt!("0x55")
+    ));
+}
+
+#[test]
+fn tampered_contract_data() {
+    let state = global_state();
+    let global_root = state.roots().global_root;
+    let mut proof = state.get_proof(&address("0x100"), &[storage_key("0x5")]).unwrap();
+    proof.contract_data.as_mut().unwrap().nonce = Nonce(stark_felt!("0x2"));
+    assert!(!verify_storage_proof(
+        &proof,
+        &global_root,
+        &address("0x100"),
+        &storage_key("0x5"),
+        &stark_felt!("0x55")
+    ));
+}
+
+#[test]
+fn missing_storage_key() {
+    let state = global_state();
+    let global_root = state.roots().global_root;
+    let proof = state.get_proof(&address("0x100"), &[storage_key("0x7")]).unwrap();
+    assert!(verify_storage_proof(
+        &proof,
+        &global_root,
+        &address("0x100"),
+        &storage_key("0x7"),
+        &stark_felt!("0x0")
+    ));
+    assert!(!verify_storage_proof(
+        &proof,
+        &global_root,
+        &address("0x100"),
+        &storage_key("0x7"),
+        &stark_felt!("0x1")
+    ));
+
+    // A contract without storage.
+    let proof = state.get_proof(&address("0x200"), &[storage_key("0x5")]).unwrap();
+    assert!(verify_storage_proof(
+        &proof,
+        &global_root,
+        &address("0x200"),
+        &storage_key("0x5"),
+        &stark_felt!("0x0")
+    ));
+}
+
+#[test]
+fn missing_contract() {
+    let state = global_state();
+    let global_root = state.roots().global_root;
+    let proof = state.get_proof(&address("0x300"), &[storage_key("0x5")]).unwrap();
+    assert!(proof.contract_data.is_none());
+    assert!(verify_storage_proof(
+        &proof,
+        &global_root,
+        &address("0x300"),
+        &storage_key("0x5"),
+        &stark_felt!("0x0")
+    ));
+    // A deployed contract cannot be proven to be missing.
+    let mut proof_of_deployed = state.get_proof(&address("0x100"), &[]).unwrap();
+    proof_of_deployed.contract_data = None;
+    assert!(!verify_storage_proof(
+        &proof_of_deployed,
+        &global_root,
+        &address("0x100"),
+        &storage_key("0x5"),
+        &stark_felt!("0x0")
+    ));
+}
+
+#[test]
+fn merkle_proof_of_single_leaf() {
+    let edge = TrieNode::Edge {
+        child: stark_felt!("0x5"),
+        path: EdgePath { path: stark_felt!("0x123"), length: 251 },
+    };
+    let root = edge.hash::<Pedersen>();
+    let proof = [edge];
+    assert_eq!(
+        verify_merkle_proof::<Pedersen>(&root, &patricia_key!("0x123"), &proof),
+        Some(stark_felt!("0x5"))
+    );
+    assert_eq!(
+        verify_merkle_proof::<Pedersen>(&root, &patricia_key!("0x124"), &proof),
+        Some(stark_felt!("0x0"))
+    );
+    assert_eq!(verify_merkle_proof::<Pedersen>(&root, &patricia_key!("0x123"), &[]), None);
+    assert_eq!(
+        verify_merkle_proof::<Pedersen>(&stark_felt!("0x1"), &patricia_key!("0x123"), &proof),
+        None
+    );
+    assert_eq!(
+        verify_merkle_proof::<Pedersen>(&StarkFelt::default(), &patricia_key!("0x123"), &[]),
+        Some(stark_felt!("0x0"))
+    );
+}
+
+#[test]
+fn proof_node_serde() {
+    let nodes = vec![
+        TrieNode::Binary { left: stark_felt!("0x1"), right: stark_felt!("0x2") },
+        TrieNode::Edge {
+            child: stark_felt!("0x3"),
+            path: EdgePath { path: stark_felt!("0x4"), length: 5 },
+        },
+    ];
+    let expected = json!([
+        { "binary": { "left": "0x1", "right": "0x2" } },
+        { "edge": { "child": "0x3", "path": { "value": "0x4", "len": 5 } } },
+    ]);
+    assert_eq!(serde_json::to_value(&nodes).unwrap(), expected);
+    assert_eq!(serde_json::from_value::<Vec<TrieNode>>(expected).unwrap(), nodes);
+}
@@ -0,0 +1,12 @@
+use std::env;
+use std::fs::read_to_string;
+use std::path::Path;
+
+/// Reads a JSON file from the `resources` directory of the crate.
+pub(crate) fn read_json_file(path_in_resource_dir: &str) -> serde_json::Value {
+    let path = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap())
+        .join("resources")
+        .join(path_in_resource_dir);
+    let json_str = read_to_string(path).unwrap();
+    serde_json::from_str(&json_str).unwrap()
+}
@@ -1,14 +1,29 @@
+#[cfg(test)]
+#[path = "transaction_test.rs"]
+mod transaction_test;
+
+use std::collections::BTreeMap;
 use std::fmt::Display;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign};
 use std::sync::Arc;
 
 use derive_more::From;
-use primitive_types::H160;
+use primitive_types::{H160, H256};
 use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
 
-use crate::block::{BlockHash, BlockNumber};
-use crate::core::{ClassHash, CompiledClassHash, ContractAddress, EntryPointSelector, Nonce};
+use crate::block::{BlockHash, BlockNumber, GasPricePerToken};
+use crate::core::{
+    ChainId, ClassHash, CompiledClassHash, ContractAddress, EntryPointSelector, Nonce,
+};
 use crate::hash::{StarkFelt, StarkHash};
-use crate::serde_utils::PrefixedBytesAsHex;
+use crate::messaging::L1ToL2Message;
+use crate::serde_utils::{
+    deserialize_u128_from_hex, deserialize_u64_from_hex, serialize_u128_as_hex,
+    serialize_u64_as_hex, PrefixedBytesAsHex,
+};
+use crate::transaction_hash::{calculate_l1_handler_transaction_hash, TransactionOptions};
 use crate::StarknetApiError;
 
 /// A transaction.
@@ -31,11 +46,44 @@ impl Transaction {
         match self {
             Transaction::Declare(tx) => tx.transaction_hash(),
             Transaction::Deploy(tx) => tx.transaction_hash,
-            Transaction::DeployAccount(tx) => tx.transaction_hash,
+            Transaction::DeployAccount(tx) => tx.transaction_hash(),
             Transaction::Invoke(tx) => tx.transaction_hash(),
             Transaction::L1Handler(tx) => tx.transaction_hash,
         }
     }
+
+    pub fn version(&self) -> TransactionVersion {
+        match self {
+            Transaction::Declare(tx) => tx.version(),
+            Transaction::Deploy(tx) => tx.version,
+            Transaction::DeployAccount(tx) => tx.version(),
+            Transaction::Invoke(tx) => tx.version(),
+            Transaction::L1Handler(tx) => tx.version,
+        }
+    }
+
+    /// Checks that the version that was signed with the transaction matches the version of the
+    /// transaction. The signed version of a declare, deploy account or invoke transaction may be a
+    /// query version.
+    pub fn validate_version(
+        &self,
+        signed_version: TransactionVersion,
+    ) -> Result<(), StarknetApiError> {
+        let version = self.version();
+        let is_account_transaction = matches!(
+            self,
+            Transaction::Declare(_) | Transaction::DeployAccount(_) | Transaction::Invoke(_)
+        );
+        if signed_version.without_query_bit() == version
+            && (is_account_transaction || !signed_version.is_query())
+        {
+            return Ok(());
+        }
+        Err(StarknetApiError::InvalidTransactionVersion {
+            version: signed_version.0,
+            expected: version.0,
+        })
+    }
 }
 
 /// A transaction output.
@@ -54,7 +102,7 @@ pub enum TransactionOutput {
 }
 
 impl TransactionOutput {
-    pub fn actual_fee(&self) -> Fee {
+    pub fn actual_fee(&self) -> FeePayment {
         match self {
             TransactionOutput::Declare(output) => output.actual_fee,
             TransactionOutput::Deploy(output) => output.actual_fee,
@@ -73,6 +121,26 @@ impl TransactionOutput {
             TransactionOutput::L1Handler(output) => &output.events,
         }
     }
+
+    pub fn execution_status(&self) -> &TransactionExecutionStatus {
+        match self {
+            TransactionOutput::Declare(output) => &output.execution_status,
+            TransactionOutput::Deploy(output) => &output.execution_status,
+            TransactionOutput::DeployAccount(output) => &output.execution_status,
+            TransactionOutput::Invoke(output) => &output.execution_status,
+            TransactionOutput::L1Handler(output) => &output.execution_status,
+        }
+    }
+
+    pub fn execution_resources(&self) -> &ExecutionResources {
+        match self {
+            TransactionOutput::Declare(output) => &output.execution_resources,
+            TransactionOutput::Deploy(output) => &output.execution_resources,
+            TransactionOutput::DeployAccount(output) => &output.execution_resources,
+            TransactionOutput::Invoke(output) => &output.execution_resources,
+            TransactionOutput::L1Handler(output) => &output.execution_resources,
+        }
+    }
 }
 
 /// A declare V0 or V1 transaction (same schema but different version).
@@ -98,11 +166,29 @@ pub struct DeclareTransactionV2 {
     pub sender_address: ContractAddress,
 }
 
+/// A declare V3 transaction.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+pub struct DeclareTransactionV3 {
+    pub transaction_hash: TransactionHash,
+    pub resource_bounds: ResourceBoundsMapping,
+    pub tip: Tip,
+    pub signature: TransactionSignature,
+    pub nonce: Nonce,
+    pub class_hash: ClassHash,
+    pub compiled_class_hash: CompiledClassHash,
+    pub sender_address: ContractAddress,
+    pub nonce_data_availability_mode: DataAvailabilityMode,
+    pub fee_data_availability_mode: DataAvailabilityMode,
+    pub paymaster_data: PaymasterData,
+    pub account_deployment_data: AccountDeploymentData,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
 pub enum DeclareTransaction {
     V0(DeclareTransactionV0V1),
     V1(DeclareTransactionV0V1),
     V2(DeclareTransactionV2),
+    V3(DeclareTransactionV3),
 }
 
 macro_rules! implement_declare_tx_getters {
@@ -112,6 +198,7 @@ macro_rules! implement_declare_tx_getters {
                 Self::V0(tx) => tx.$field.clone(),
                 Self::V1(tx) => tx.$field.clone(),
                 Self::V2(tx) => tx.$field.clone(),
+                Self::V3(tx) => tx.$field.clone(),
             }
         })*
     };
@@ -123,22 +210,32 @@ impl DeclareTransaction {
         (class_hash, ClassHash),
         (nonce, Nonce),
         (sender_address, ContractAddress),
-        (max_fee, Fee),
         (signature, TransactionSignature)
     );
 
+    /// Returns the max fee of the transaction, or `None` for a V3 transaction, whose fee is
+    /// bounded by its resource bounds.
+    pub fn max_fee(&self) -> Option<Fee> {
+        match self {
+            DeclareTransaction::V0(tx) | DeclareTransaction::V1(tx) => Some(tx.max_fee),
+            DeclareTransaction::V2(tx) => Some(tx.max_fee),
+            DeclareTransaction::V3(_) => None,
+        }
+    }
+
     pub fn version(&self) -> TransactionVersion {
         match self {
-            DeclareTransaction::V0(_) => TransactionVersion(StarkFelt::from(0_u8)),
-            DeclareTransaction::V1(_) => TransactionVersion(StarkFelt::from(1_u8)),
-            DeclareTransaction::V2(_) => TransactionVersion(StarkFelt::from(2_u8)),
+            DeclareTransaction::V0(_) => TransactionVersion::ZERO,
+            DeclareTransaction::V1(_) => TransactionVersion::ONE,
+            DeclareTransaction::V2(_) => TransactionVersion::TWO,
+            DeclareTransaction::V3(_) => TransactionVersion::THREE,
         }
     }
 }
 
-/// A deploy account transaction.
-#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
-pub struct DeployAccountTransaction {
+/// A deploy account V1 transaction.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+pub struct DeployAccountTransactionV1 {
     pub transaction_hash: TransactionHash,
     pub max_fee: Fee,
     pub version: TransactionVersion,
@@ -150,6 +247,87 @@ pub struct DeployAccountTransaction {
     pub constructor_calldata: Calldata,
 }
 
+impl Default for DeployAccountTransactionV1 {
+    fn default() -> Self {
+        Self {
+            transaction_hash: TransactionHash::default(),
+            max_fee: Fee::default(),
+            version: TransactionVersion::ONE,
+            signature: TransactionSignature::default(),
+            nonce: Nonce::default(),
+            class_hash: ClassHash::default(),
+            contract_address: ContractAddress::default(),
+            contract_address_salt: ContractAddressSalt::default(),
+            constructor_calldata: Calldata::default(),
+        }
+    }
+}
+
+/// A deploy account V3 transaction.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+pub struct DeployAccountTransactionV3 {
+    pub transaction_hash: TransactionHash,
+    pub resource_bounds: ResourceBoundsMapping,
+    pub tip: Tip,
+    pub signature: TransactionSignature,
+    pub nonce: Nonce,
+    pub class_hash: ClassHash,
+    pub contract_address: ContractAddress,
+    pub contract_address_salt: ContractAddressSalt,
+    pub constructor_calldata: Calldata,
+    pub nonce_data_availability_mode: DataAvailabilityMode,
+    pub fee_data_availability_mode: DataAvailabilityMode,
+    pub paymaster_data: PaymasterData,
+}
+
+/// A deploy account transaction. Serialized as the transaction of its version, which is told
+/// apart by its fee fields.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord, From)]
+#[serde(untagged)]
+pub enum DeployAccountTransaction {
+    V1(DeployAccountTransactionV1),
+    V3(DeployAccountTransactionV3),
+}
+
+macro_rules! implement_deploy_account_tx_getters {
+    ($(($field:ident, $field_type:ty)),*) => {
+        $(pub fn $field(&self) -> $field_type {
+            match self {
+                Self::V1(tx) => tx.$field.clone(),
+                Self::V3(tx) => tx.$field.clone(),
+            }
+        })*
+    };
+}
+
+impl DeployAccountTransaction {
+    implement_deploy_account_tx_getters!(
+        (transaction_hash, TransactionHash),
+        (signature, TransactionSignature),
+        (nonce, Nonce),
+        (class_hash, ClassHash),
+        (contract_address, ContractAddress),
+        (contract_address_salt, ContractAddressSalt),
+        (constructor_calldata, Calldata)
+    );
+
+    /// Returns the max fee of the transaction, or `None` for a V3 transaction, whose fee is
+    /// bounded by its resource bounds.
+    pub fn max_fee(&self) -> Option<Fee> {
+        match self {
+            DeployAccountTransaction::V1(tx) => Some(tx.max_fee),
+            DeployAccountTransaction::V3(_) => None,
+        }
+    }
+
+    pub fn version(&self) -> TransactionVersion {
+        match self {
+            DeployAccountTransaction::V1(tx) => tx.version,
+            DeployAccountTransaction::V3(_) => TransactionVersion::THREE,
+        }
+    }
+}
+
 /// A deploy transaction.
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
 pub struct DeployTransaction {
@@ -184,10 +362,27 @@ pub struct InvokeTransactionV1 {
     pub calldata: Calldata,
 }
 
+/// An invoke V3 transaction.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+pub struct InvokeTransactionV3 {
+    pub transaction_hash: TransactionHash,
+    pub resource_bounds: ResourceBoundsMapping,
+    pub tip: Tip,
+    pub signature: TransactionSignature,
+    pub nonce: Nonce,
+    pub sender_address: ContractAddress,
+    pub calldata: Calldata,
+    pub nonce_data_availability_mode: DataAvailabilityMode,
+    pub fee_data_availability_mode: DataAvailabilityMode,
+    pub paymaster_data: PaymasterData,
+    pub account_deployment_data: AccountDeploymentData,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord, From)]
 pub enum InvokeTransaction {
     V0(InvokeTransactionV0),
     V1(InvokeTransactionV1),
+    V3(InvokeTransactionV3),
 }
 
 macro_rules! implement_invoke_tx_getters {
@@ -196,6 +391,7 @@ macro_rules! implement_invoke_tx_getters {
             match self {
                 Self::V0(tx) => tx.$field.clone(),
                 Self::V1(tx) => tx.$field.clone(),
+                Self::V3(tx) => tx.$field.clone(),
             }
         })*
     };
@@ -204,12 +400,29 @@ macro_rules! implement_invoke_tx_getters {
 impl InvokeTransaction {
     implement_invoke_tx_getters!(
         (transaction_hash, TransactionHash),
-        (max_fee, Fee),
         (signature, TransactionSignature),
         (nonce, Nonce),
         (sender_address, ContractAddress),
         (calldata, Calldata)
     );
+
+    /// Returns the max fee of the transaction, or `None` for a V3 transaction, whose fee is
+    /// bounded by its resource bounds.
+    pub fn max_fee(&self) -> Option<Fee> {
+        match self {
+            InvokeTransaction::V0(tx) => Some(tx.max_fee),
+            InvokeTransaction::V1(tx) => Some(tx.max_fee),
+            InvokeTransaction::V3(_) => None,
+        }
+    }
+
+    pub fn version(&self) -> TransactionVersion {
+        match self {
+            InvokeTransaction::V0(_) => TransactionVersion::ZERO,
+            InvokeTransaction::V1(_) => TransactionVersion::ONE,
+            InvokeTransaction::V3(_) => TransactionVersion::THREE,
+        }
+    }
 }
 
 /// An L1 handler transaction.
@@ -223,44 +436,304 @@ pub struct L1HandlerTransaction {
     pub calldata: Calldata,
 }
 
+impl L1HandlerTransaction {
+    /// Returns the transaction that handles a message from L1, with its hash on the given chain.
+    /// The calldata is the sender of the message followed by the payload.
+    pub fn from_message(
+        message: &L1ToL2Message,
+        chain_id: &ChainId,
+    ) -> Result<Self, StarknetApiError> {
+        let calldata = std::iter::once(StarkFelt::from(message.message.from_address))
+            .chain(message.message.payload.0.iter().copied())
+            .collect::<Vec<_>>();
+        let mut transaction = Self {
+            transaction_hash: TransactionHash::default(),
+            version: TransactionVersion::ZERO,
+            nonce: message.nonce,
+            contract_address: message.to_address,
+            entry_point_selector: message.selector,
+            calldata: Calldata(calldata.into()),
+        };
+        transaction.transaction_hash = calculate_l1_handler_transaction_hash(
+            &transaction,
+            chain_id,
+            &TransactionOptions::default(),
+        )?;
+        Ok(transaction)
+    }
+
+    /// Returns the hash of the L1 to L2 message that the transaction consumed.
+    pub fn message_hash(&self) -> Result<H256, StarknetApiError> {
+        Ok(MessageToL2::try_from(self)?.hash(
+            self.nonce,
+            self.contract_address,
+            self.entry_point_selector,
+        ))
+    }
+}
+
 /// A declare transaction output.
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
 pub struct DeclareTransactionOutput {
-    pub actual_fee: Fee,
+    pub actual_fee: FeePayment,
     pub messages_sent: Vec<MessageToL1>,
     pub events: Vec<Event>,
+    #[serde(flatten)]
+    pub execution_status: TransactionExecutionStatus,
+    #[serde(default)]
+    pub execution_resources: ExecutionResources,
 }
 
 /// A deploy-account transaction output.
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
 pub struct DeployAccountTransactionOutput {
-    pub actual_fee: Fee,
+    pub actual_fee: FeePayment,
     pub messages_sent: Vec<MessageToL1>,
     pub events: Vec<Event>,
+    #[serde(flatten)]
+    pub execution_status: TransactionExecutionStatus,
+    #[serde(default)]
+    pub execution_resources: ExecutionResources,
 }
 
 /// A deploy transaction output.
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
 pub struct DeployTransactionOutput {
-    pub actual_fee: Fee,
+    pub actual_fee: FeePayment,
     pub messages_sent: Vec<MessageToL1>,
     pub events: Vec<Event>,
+    #[serde(flatten)]
+    pub execution_status: TransactionExecutionStatus,
+    #[serde(default)]
+    pub execution_resources: ExecutionResources,
 }
 
 /// An invoke transaction output.
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
 pub struct InvokeTransactionOutput {
-    pub actual_fee: Fee,
+    pub actual_fee: FeePayment,
     pub messages_sent: Vec<MessageToL1>,
     pub events: Vec<Event>,
+    #[serde(flatten)]
+    pub execution_status: TransactionExecutionStatus,
+    #[serde(default)]
+    pub execution_resources: ExecutionResources,
 }
 
 /// An L1 handler transaction output.
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
 pub struct L1HandlerTransactionOutput {
-    pub actual_fee: Fee,
+    pub actual_fee: FeePayment,
     pub messages_sent: Vec<MessageToL1>,
     pub events: Vec<Event>,
+    #[serde(flatten)]
+    pub execution_status: TransactionExecutionStatus,
+    #[serde(default)]
+    pub execution_resources: ExecutionResources,
+}
+
+/// The execution status of a transaction. A reverted transaction is included in the block and
+/// pays its fee, but its state changes, other than the fee transfer, are discarded. Transactions
+/// from before reverted transactions existed have no execution status, and succeeded.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[serde(tag = "execution_status", try_from = "RawTransactionExecutionStatus")]
+pub enum TransactionExecutionStatus {
+    #[serde(rename = "SUCCEEDED")]
+    #[default]
+    Succeeded,
+    #[serde(rename = "REVERTED")]
+    Reverted {
+        #[serde(rename = "revert_reason")]
+        reason: String,
+    },
+}
+
+#[derive(Deserialize)]
+struct RawTransactionExecutionStatus {
+    #[serde(default)]
+    execution_status: Option<String>,
+    #[serde(default)]
+    revert_reason: Option<String>,
+}
+
+impl TryFrom<RawTransactionExecutionStatus> for TransactionExecutionStatus {
+    type Error = String;
+
+    fn try_from(raw: RawTransactionExecutionStatus) -> Result<Self, Self::Error> {
+        match (raw.execution_status.as_deref(), raw.revert_reason) {
+            (None | Some("SUCCEEDED"), _) => Ok(Self::Succeeded),
+            (Some("REVERTED"), Some(reason)) => Ok(Self::Reverted { reason }),
+            (Some("REVERTED"), None) => Err("A reverted transaction has no revert_reason.".into()),
+            (Some(status), _) => Err(format!("Unknown execution status {status}.")),
+        }
+    }
+}
+
+/// The resources used by the execution of a transaction. Resources of several transactions can
+/// be aggregated by adding them.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[serde(try_from = "RawExecutionResources")]
+pub struct ExecutionResources {
+    pub steps: u64,
+    pub memory_holes: u64,
+    /// The number of instances of each builtin. A builtin that was not used may be missing.
+    #[serde(flatten)]
+    pub builtin_instance_counter: BTreeMap<Builtin, u64>,
+    pub data_availability: DataAvailabilityResources,
+}
+
+// The execution resources as serialized by the JSON-RPC API, with a field per builtin, or by the
+// feeder gateway, with `n_steps`, `n_memory_holes` and the builtins in `builtin_instance_counter`.
+#[derive(Deserialize)]
+struct RawExecutionResources {
+    #[serde(alias = "n_steps")]
+    steps: u64,
+    #[serde(default, alias = "n_memory_holes")]
+    memory_holes: u64,
+    #[serde(default)]
+    builtin_instance_counter: BTreeMap<Builtin, u64>,
+    #[serde(default)]
+    data_availability: DataAvailabilityResources,
+    #[serde(flatten)]
+    other_fields: BTreeMap<String, serde_json::Value>,
+}
+
+impl TryFrom<RawExecutionResources> for ExecutionResources {
+    type Error = String;
+
+    fn try_from(raw: RawExecutionResources) -> Result<Self, Self::Error> {
+        let mut builtin_instance_counter = raw.builtin_instance_counter;
+        // Fields that are not builtins, such as gas amounts, are ignored.
+        for (key, value) in raw.other_fields {
+            if !key.ends_with("_builtin_applications") && !key.ends_with("_builtin") {
+                continue;
+            }
+            let count = value.as_u64().ok_or_else(|| format!("Invalid count {value} of {key}."))?;
+            *builtin_instance_counter.entry(Builtin::from(key)).or_default() += count;
+        }
+        Ok(Self {
+            steps: raw.steps,
+            memory_holes: raw.memory_holes,
+            builtin_instance_counter,
+            data_availability: raw.data_availability,
+        })
+    }
+}
+
+impl ExecutionResources {
+    /// Returns the number of instances of a builtin.
+    pub fn builtin_count(&self, builtin: Builtin) -> u64 {
+        self.builtin_instance_counter.get(&builtin).copied().unwrap_or_default()
+    }
+}
+
+impl AddAssign<&ExecutionResources> for ExecutionResources {
+    fn add_assign(&mut self, other: &ExecutionResources) {
+        self.steps += other.steps;
+        self.memory_holes += other.memory_holes;
+        for (builtin, count) in &other.builtin_instance_counter {
+            *self.builtin_instance_counter.entry(builtin.clone()).or_default() += count;
+        }
+        self.data_availability.l1_gas += other.data_availability.l1_gas;
+        self.data_availability.l1_data_gas += other.data_availability.l1_data_gas;
+    }
+}
+
+impl Add<&ExecutionResources> for ExecutionResources {
+    type Output = ExecutionResources;
+
+    fn add(mut self, other: &ExecutionResources) -> ExecutionResources {
+        self += other;
+        self
+    }
+}
+
+impl<'a> Sum<&'a ExecutionResources> for ExecutionResources {
+    fn sum<I: Iterator<Item = &'a ExecutionResources>>(iter: I) -> Self {
+        iter.fold(ExecutionResources::default(), |total, resources| total + resources)
+    }
+}
+
+/// A builtin of the Cairo VM, serialized as the key of its instance count in
+/// [`ExecutionResources`]: `<name>_builtin_applications`, or `segment_arena_builtin`. The keys
+/// `<name>_builtin` of the feeder gateway are also accepted.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[serde(from = "String", into = "String")]
+pub enum Builtin {
+    Pedersen,
+    RangeCheck,
+    Ecdsa,
+    Bitwise,
+    EcOp,
+    Keccak,
+    Poseidon,
+    SegmentArena,
+    RangeCheck96,
+    AddMod,
+    MulMod,
+    /// A builtin that is not known to this crate, by its name.
+    Other(String),
+}
+
+impl Builtin {
+    /// Returns the name of the builtin in the Cairo VM.
+    pub fn name(&self) -> &str {
+        match self {
+            Builtin::Pedersen => "pedersen",
+            Builtin::RangeCheck => "range_check",
+            Builtin::Ecdsa => "ecdsa",
+            Builtin::Bitwise => "bitwise",
+            Builtin::EcOp => "ec_op",
+            Builtin::Keccak => "keccak",
+            Builtin::Poseidon => "poseidon",
+            Builtin::SegmentArena => "segment_arena",
+            Builtin::RangeCheck96 => "range_check96",
+            Builtin::AddMod => "add_mod",
+            Builtin::MulMod => "mul_mod",
+            Builtin::Other(name) => name,
+        }
+    }
+}
+
+impl From<String> for Builtin {
+    fn from(key: String) -> Self {
+        let name = key
+            .strip_suffix("_builtin_applications")
+            .or_else(|| key.strip_suffix("_builtin"))
+            .unwrap_or(&key);
+        match name {
+            "pedersen" => Builtin::Pedersen,
+            "range_check" => Builtin::RangeCheck,
+            "ecdsa" => Builtin::Ecdsa,
+            "bitwise" => Builtin::Bitwise,
+            "ec_op" => Builtin::EcOp,
+            "keccak" => Builtin::Keccak,
+            "poseidon" => Builtin::Poseidon,
+            "segment_arena" => Builtin::SegmentArena,
+            "range_check96" => Builtin::RangeCheck96,
+            "add_mod" => Builtin::AddMod,
+            "mul_mod" => Builtin::MulMod,
+            _ => Builtin::Other(name.to_owned()),
+        }
+    }
+}
+
+impl From<Builtin> for String {
+    fn from(builtin: Builtin) -> Self {
+        match builtin {
+            Builtin::SegmentArena => "segment_arena_builtin".to_owned(),
+            _ => format!("{}_builtin_applications", builtin.name()),
+        }
+    }
+}
+
+/// The gas used to publish the state diff of a transaction on L1.
+#[derive(
+    Debug, Copy, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord,
+)]
+pub struct DataAvailabilityResources {
+    pub l1_gas: u64,
+    pub l1_data_gas: u64,
 }
 
 /// A transaction receipt.
@@ -298,6 +771,160 @@ impl From<Fee> for StarkFelt {
     }
 }
 
+impl Fee {
+    pub fn checked_add(self, other: Fee) -> Option<Fee> {
+        self.0.checked_add(other.0).map(Fee)
+    }
+}
+
+/// The unit of a fee: WEI for fees paid in ETH and FRI for fees paid in STRK.
+#[derive(
+    Debug, Copy, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord,
+)]
+pub enum PriceUnit {
+    #[serde(rename = "WEI")]
+    #[default]
+    Wei,
+    #[serde(rename = "FRI")]
+    Fri,
+}
+
+/// A fee together with its unit. A fee that is serialized as an amount only, as in receipts from
+/// before Starknet v0.13.1, is in Wei.
+#[derive(
+    Debug, Copy, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord,
+)]
+#[serde(from = "RawFeePayment")]
+pub struct FeePayment {
+    pub amount: Fee,
+    pub unit: PriceUnit,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawFeePayment {
+    WithUnit { amount: Fee, unit: PriceUnit },
+    Amount(Fee),
+}
+
+impl From<RawFeePayment> for FeePayment {
+    fn from(raw: RawFeePayment) -> Self {
+        match raw {
+            RawFeePayment::WithUnit { amount, unit } => Self { amount, unit },
+            RawFeePayment::Amount(amount) => Self { amount, unit: PriceUnit::Wei },
+        }
+    }
+}
+
+impl FeePayment {
+    /// Returns the fee of the given amount of gas, at the gas price of the given unit. Returns
+    /// `None` on overflow.
+    pub fn from_gas(gas: u128, gas_prices: &GasPricePerToken, unit: PriceUnit) -> Option<Self> {
+        let amount = gas.checked_mul(gas_prices.get(unit).0)?;
+        Some(Self { amount: Fee(amount), unit })
+    }
+
+    /// Returns the sum of two fees, or `None` if their units differ or on overflow.
+    pub fn checked_add(self, other: FeePayment) -> Option<Self> {
+        if self.unit != other.unit {
+            return None;
+        }
+        Some(Self { amount: self.amount.checked_add(other.amount)?, unit: self.unit })
+    }
+
+    /// Converts the fee to the given unit, through the amount of gas it pays for at the given gas
+    /// prices. The amount of gas is rounded down. Returns `None` if the gas price of the unit of
+    /// the fee is 0 or on overflow.
+    pub fn convert(self, gas_prices: &GasPricePerToken, unit: PriceUnit) -> Option<Self> {
+        if self.unit == unit {
+            return Some(self);
+        }
+        let gas = self.amount.0.checked_div(gas_prices.get(self.unit).0)?;
+        Self::from_gas(gas, gas_prices, unit)
+    }
+}
+
+/// A resource whose usage by a transaction is bounded by the sender.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+pub enum Resource {
+    #[serde(rename = "l1_gas")]
+    L1Gas,
+    #[serde(rename = "l2_gas")]
+    L2Gas,
+}
+
+/// The maximal amount of a resource that a transaction may use, and the maximal price per unit
+/// of the resource that the sender is willing to pay.
+#[derive(
+    Debug, Copy, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord,
+)]
+pub struct ResourceBounds {
+    #[serde(
+        serialize_with = "serialize_u64_as_hex",
+        deserialize_with = "deserialize_u64_from_hex"
+    )]
+    pub max_amount: u64,
+    #[serde(
+        serialize_with = "serialize_u128_as_hex",
+        deserialize_with = "deserialize_u128_from_hex"
+    )]
+    pub max_price_per_unit: u128,
+}
+
+/// The bounds of the resources of a V3 transaction.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+pub struct ResourceBoundsMapping(pub BTreeMap<Resource, ResourceBounds>);
+
+impl ResourceBoundsMapping {
+    /// Returns the bounds of a resource, which are 0 if the resource is not bounded.
+    pub fn get(&self, resource: Resource) -> ResourceBounds {
+        self.0.get(&resource).copied().unwrap_or_default()
+    }
+}
+
+/// A tip paid to the sequencer on top of the fee, per unit of L2 gas.
+#[derive(
+    Debug, Copy, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord,
+)]
+#[serde(from = "PrefixedBytesAsHex<8_usize>", into = "PrefixedBytesAsHex<8_usize>")]
+pub struct Tip(pub u64);
+
+impl From<PrefixedBytesAsHex<8_usize>> for Tip {
+    fn from(val: PrefixedBytesAsHex<8_usize>) -> Self {
+        Self(u64::from_be_bytes(val.0))
+    }
+}
+
+impl From<Tip> for PrefixedBytesAsHex<8_usize> {
+    fn from(tip: Tip) -> Self {
+        Self(tip.0.to_be_bytes())
+    }
+}
+
+impl From<Tip> for StarkFelt {
+    fn from(tip: Tip) -> Self {
+        Self::from(tip.0)
+    }
+}
+
+/// The data passed to the paymaster of a V3 transaction.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+pub struct PaymasterData(pub Vec<StarkFelt>);
+
+/// The data used to deploy the sender account of a V3 transaction.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+pub struct AccountDeploymentData(pub Vec<StarkFelt>);
+
+/// The layer on which the data of a storage domain is published.
+#[derive(
+    Debug, Copy, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord,
+)]
+pub enum DataAvailabilityMode {
+    #[default]
+    L1,
+    L2,
+}
+
 /// The hash of a [Transaction](`crate::transaction::Transaction`).
 #[derive(
     Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord,
@@ -326,6 +953,50 @@ pub struct TransactionSignature(pub Vec<StarkFelt>);
 )]
 pub struct TransactionVersion(pub StarkFelt);
 
+impl TransactionVersion {
+    pub const ZERO: Self = Self::new(0, false);
+    pub const ONE: Self = Self::new(1, false);
+    pub const TWO: Self = Self::new(2, false);
+    pub const THREE: Self = Self::new(3, false);
+    pub const ZERO_QUERY: Self = Self::new(0, true);
+    pub const ONE_QUERY: Self = Self::new(1, true);
+    pub const TWO_QUERY: Self = Self::new(2, true);
+    pub const THREE_QUERY: Self = Self::new(3, true);
+
+    const fn new(version: u8, query: bool) -> Self {
+        let mut bytes = [0u8; 32];
+        bytes[QUERY_BIT_BYTE] = if query { QUERY_BIT_MASK } else { 0 };
+        bytes[31] = version;
+        Self(StarkFelt::from_bytes_unchecked(bytes))
+    }
+
+    /// Returns true if this is the version of a transaction that is only simulated, which is the
+    /// version of the transaction plus 2^128.
+    pub fn is_query(&self) -> bool {
+        self.0.bytes()[QUERY_BIT_BYTE] & QUERY_BIT_MASK != 0
+    }
+
+    /// Returns the query version of this version, which is the version plus 2^128.
+    pub fn with_query_bit(&self) -> Self {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(self.0.bytes());
+        bytes[QUERY_BIT_BYTE] |= QUERY_BIT_MASK;
+        Self(StarkFelt::from_bytes_unchecked(bytes))
+    }
+
+    /// Returns the version without the 2^128 offset of query versions.
+    pub fn without_query_bit(&self) -> Self {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(self.0.bytes());
+        bytes[QUERY_BIT_BYTE] &= !QUERY_BIT_MASK;
+        Self(StarkFelt::from_bytes_unchecked(bytes))
+    }
+}
+
+// The position of bit 128 of a big-endian felt, which marks query versions.
+const QUERY_BIT_BYTE: usize = 15;
+const QUERY_BIT_MASK: u8 = 1;
+
 /// The calldata of a transaction.
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
 pub struct Calldata(pub Arc<Vec<StarkFelt>>);
@@ -344,6 +1015,42 @@ pub struct MessageToL2 {
     pub payload: L1ToL2Payload,
 }
 
+impl MessageToL2 {
+    /// Returns the hash of the message, as computed by the Starknet core contract on L1:
+    /// the Keccak-256 hash of `from_address`, `to_address`, `nonce`, `selector`, the length of the
+    /// payload and the payload, each encoded as a 32-byte word.
+    pub fn hash(
+        &self,
+        nonce: Nonce,
+        to_address: ContractAddress,
+        selector: EntryPointSelector,
+    ) -> H256 {
+        let mut hasher = Keccak256::new();
+        hasher.update(H256::from(self.from_address.0));
+        hasher.update(to_address.0.key().bytes());
+        hasher.update(nonce.0.bytes());
+        hasher.update(selector.0.bytes());
+        update_with_payload(&mut hasher, &self.payload.0);
+        H256(hasher.finalize().into())
+    }
+}
+
+/// The message consumed by an L1 handler transaction: the first element of the calldata is the
+/// sender of the message and the rest is the payload.
+impl TryFrom<&L1HandlerTransaction> for MessageToL2 {
+    type Error = StarknetApiError;
+
+    fn try_from(transaction: &L1HandlerTransaction) -> Result<Self, Self::Error> {
+        let Some((from_address, payload)) = transaction.calldata.0.split_first() else {
+            return Err(StarknetApiError::MissingL1SenderAddress);
+        };
+        Ok(Self {
+            from_address: EthAddress::try_from(*from_address)?,
+            payload: L1ToL2Payload(payload.to_vec()),
+        })
+    }
+}
+
 /// An L2 to L1 message.
 #[derive(Debug, Default, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
 pub struct MessageToL1 {
@@ -352,12 +1059,40 @@ pub struct MessageToL1 {
     pub payload: L2ToL1Payload,
 }
 
+impl MessageToL1 {
+    /// Returns the hash of the message, as computed by the Starknet core contract on L1:
+    /// the Keccak-256 hash of `from_address`, `to_address`, the length of the payload and the
+    /// payload, each encoded as a 32-byte word.
+    pub fn hash(&self) -> H256 {
+        let mut hasher = Keccak256::new();
+        hasher.update(self.from_address.0.key().bytes());
+        hasher.update(H256::from(self.to_address.0));
+        update_with_payload(&mut hasher, &self.payload.0);
+        H256(hasher.finalize().into())
+    }
+}
+
+fn update_with_payload(hasher: &mut Keccak256, payload: &[StarkFelt]) {
+    hasher.update(StarkFelt::from(payload.len() as u64).bytes());
+    for felt in payload {
+        hasher.update(felt.bytes());
+    }
+}
+
 /// An Ethereum address.
 #[derive(
     Debug, Copy, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord,
 )]
 pub struct EthAddress(pub H160);
 
+impl From<EthAddress> for StarkFelt {
+    fn from(address: EthAddress) -> Self {
+        let mut bytes = [0u8; 32];
+        bytes[32 - H160::len_bytes()..].copy_from_slice(address.0.as_bytes());
+        StarkFelt::new(bytes).expect("An Ethereum address should fit in a StarkFelt.")
+    }
+}
+
 impl TryFrom<StarkFelt> for EthAddress {
     type Error = StarknetApiError;
     fn try_from(felt: StarkFelt) -> Result<Self, Self::Error> {
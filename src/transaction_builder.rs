@@ -0,0 +1,297 @@
+//! Builders of [transactions](`crate::transaction`), which check that the required fields are set
+//! and compute the transaction hash and, where needed, the contract address and the signature.
+#[cfg(test)]
+#[path = "transaction_builder_test.rs"]
+mod transaction_builder_test;
+
+use crate::core::{
+    calculate_contract_address, ChainId, ClassHash, CompiledClassHash, ContractAddress,
+    EntryPointSelector, Nonce,
+};
+use crate::crypto::TransactionSigner;
+use crate::hash::Pedersen;
+use crate::transaction::{
+    AccountDeploymentData, Calldata, ContractAddressSalt, DataAvailabilityMode,
+    DeclareTransactionV0V1, DeclareTransactionV2, DeclareTransactionV3, DeployAccountTransactionV1,
+    DeployAccountTransactionV3, DeployTransaction, Fee, InvokeTransactionV0, InvokeTransactionV1,
+    InvokeTransactionV3, L1HandlerTransaction, PaymasterData, ResourceBoundsMapping, Tip,
+    TransactionSignature, TransactionVersion,
+};
+use crate::transaction_hash::{
+    calculate_declare_v0_transaction_hash, calculate_declare_v1_transaction_hash,
+    calculate_declare_v2_transaction_hash, calculate_declare_v3_transaction_hash,
+    calculate_deploy_account_v1_transaction_hash, calculate_deploy_account_v3_transaction_hash,
+    calculate_deploy_transaction_hash, calculate_invoke_v0_transaction_hash,
+    calculate_invoke_v1_transaction_hash, calculate_invoke_v3_transaction_hash,
+    calculate_l1_handler_transaction_hash, TransactionOptions,
+};
+use crate::StarknetApiError;
+
+// Defines a builder with a setter per field. Building fails if a required field is not set, and
+// an optional field that is not set gets its default value. If `deployed_by` is given, the
+// contract address is calculated from the class hash, the salt and the constructor calldata.
+macro_rules! transaction_builder {
+    (
+        $(#[$doc:meta])*
+        $builder:ident => $transaction:ident {
+            required: { $($required:ident: $required_type:ty),* $(,)? },
+            optional: { $($optional:ident: $optional_type:ty),* $(,)? },
+            hash: $calculate_hash:path
+            $(, deployed_by: $deployer_address:expr)? $(,)?
+        }
+    ) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Default)]
+        pub struct $builder {
+            $($required: Option<$required_type>,)*
+            $($optional: $optional_type,)*
+        }
+
+        impl $builder {
+            $(
+                pub fn $required(mut self, $required: $required_type) -> Self {
+                    self.$required = Some($required);
+                    self
+                }
+            )*
+
+            $(
+                pub fn $optional(mut self, $optional: $optional_type) -> Self {
+                    self.$optional = $optional;
+                    self
+                }
+            )*
+
+            /// Returns the transaction, with its hash on the given chain.
+            pub fn build(self, chain_id: &ChainId) -> Result<$transaction, StarknetApiError> {
+                self.build_with_options(chain_id, &TransactionOptions::default())
+            }
+
+            /// Returns the transaction, with its hash on the given chain and with the given
+            /// options.
+            pub fn build_with_options(
+                self,
+                chain_id: &ChainId,
+                options: &TransactionOptions,
+            ) -> Result<$transaction, StarknetApiError> {
+                let mut transaction = $transaction {
+                    $(
+                        $required: self.$required.ok_or(
+                            StarknetApiError::MissingTransactionField {
+                                field: stringify!($required),
+                            },
+                        )?,
+                    )*
+                    $($optional: self.$optional,)*
+                    ..Default::default()
+                };
+                $(
+                    transaction.contract_address = calculate_contract_address::<Pedersen>(
+                        transaction.contract_address_salt,
+                        transaction.class_hash,
+                        &transaction.constructor_calldata,
+                        $deployer_address,
+                    )?;
+                )?
+                transaction.transaction_hash = $calculate_hash(&transaction, chain_id, options)?;
+                Ok(transaction)
+            }
+        }
+    };
+}
+
+// Adds a method that builds the transaction and signs its hash.
+macro_rules! signed_transaction_builder {
+    ($builder:ident => $transaction:ident) => {
+        impl $builder {
+            /// Returns the transaction, with its hash on the given chain and the signature of the
+            /// hash by `signer`.
+            pub fn build_and_sign(
+                self,
+                chain_id: &ChainId,
+                signer: &impl TransactionSigner,
+            ) -> Result<$transaction, StarknetApiError> {
+                self.build_and_sign_with_options(chain_id, &TransactionOptions::default(), signer)
+            }
+
+            /// Returns the transaction, with its hash on the given chain and with the given
+            /// options, and the signature of the hash by `signer`.
+            pub fn build_and_sign_with_options(
+                self,
+                chain_id: &ChainId,
+                options: &TransactionOptions,
+                signer: &impl TransactionSigner,
+            ) -> Result<$transaction, StarknetApiError> {
+                let mut transaction = self.build_with_options(chain_id, options)?;
+                transaction.signature = signer.sign(&transaction.transaction_hash)?;
+                Ok(transaction)
+            }
+        }
+    };
+}
+
+transaction_builder!(
+    /// A builder of a declare V0 transaction.
+    DeclareTransactionV0Builder => DeclareTransactionV0V1 {
+        required: { sender_address: ContractAddress, class_hash: ClassHash, max_fee: Fee },
+        optional: { nonce: Nonce, signature: TransactionSignature },
+        hash: calculate_declare_v0_transaction_hash,
+    }
+);
+signed_transaction_builder!(DeclareTransactionV0Builder => DeclareTransactionV0V1);
+
+transaction_builder!(
+    /// A builder of a declare V1 transaction.
+    DeclareTransactionV1Builder => DeclareTransactionV0V1 {
+        required: { sender_address: ContractAddress, class_hash: ClassHash, max_fee: Fee },
+        optional: { nonce: Nonce, signature: TransactionSignature },
+        hash: calculate_declare_v1_transaction_hash,
+    }
+);
+signed_transaction_builder!(DeclareTransactionV1Builder => DeclareTransactionV0V1);
+
+transaction_builder!(
+    /// A builder of a [`DeclareTransactionV2`].
+    DeclareTransactionV2Builder => DeclareTransactionV2 {
+        required: {
+            sender_address: ContractAddress,
+            class_hash: ClassHash,
+            compiled_class_hash: CompiledClassHash,
+            max_fee: Fee,
+        },
+        optional: { nonce: Nonce, signature: TransactionSignature },
+        hash: calculate_declare_v2_transaction_hash,
+    }
+);
+signed_transaction_builder!(DeclareTransactionV2Builder => DeclareTransactionV2);
+
+transaction_builder!(
+    /// A builder of a [`DeclareTransactionV3`].
+    DeclareTransactionV3Builder => DeclareTransactionV3 {
+        required: {
+            sender_address: ContractAddress,
+            class_hash: ClassHash,
+            compiled_class_hash: CompiledClassHash,
+            resource_bounds: ResourceBoundsMapping,
+        },
+        optional: {
+            nonce: Nonce,
+            signature: TransactionSignature,
+            tip: Tip,
+            nonce_data_availability_mode: DataAvailabilityMode,
+            fee_data_availability_mode: DataAvailabilityMode,
+            paymaster_data: PaymasterData,
+            account_deployment_data: AccountDeploymentData,
+        },
+        hash: calculate_declare_v3_transaction_hash,
+    }
+);
+signed_transaction_builder!(DeclareTransactionV3Builder => DeclareTransactionV3);
+
+transaction_builder!(
+    /// A builder of a [`DeployAccountTransactionV1`]. The address of the account is calculated
+    /// from the class hash, the salt and the constructor calldata.
+    DeployAccountTransactionV1Builder => DeployAccountTransactionV1 {
+        required: { class_hash: ClassHash, max_fee: Fee },
+        optional: {
+            nonce: Nonce,
+            signature: TransactionSignature,
+            contract_address_salt: ContractAddressSalt,
+            constructor_calldata: Calldata,
+        },
+        hash: calculate_deploy_account_v1_transaction_hash,
+        deployed_by: ContractAddress::default(),
+    }
+);
+signed_transaction_builder!(DeployAccountTransactionV1Builder => DeployAccountTransactionV1);
+
+transaction_builder!(
+    /// A builder of a [`DeployAccountTransactionV3`]. The address of the account is calculated
+    /// from the class hash, the salt and the constructor calldata.
+    DeployAccountTransactionV3Builder => DeployAccountTransactionV3 {
+        required: { class_hash: ClassHash, resource_bounds: ResourceBoundsMapping },
+        optional: {
+            nonce: Nonce,
+            signature: TransactionSignature,
+            contract_address_salt: ContractAddressSalt,
+            constructor_calldata: Calldata,
+            tip: Tip,
+            nonce_data_availability_mode: DataAvailabilityMode,
+            fee_data_availability_mode: DataAvailabilityMode,
+            paymaster_data: PaymasterData,
+        },
+        hash: calculate_deploy_account_v3_transaction_hash,
+        deployed_by: ContractAddress::default(),
+    }
+);
+signed_transaction_builder!(DeployAccountTransactionV3Builder => DeployAccountTransactionV3);
+
+transaction_builder!(
+    /// A builder of a [`DeployTransaction`]. The address of the contract is calculated from the
+    /// class hash, the salt and the constructor calldata.
+    DeployTransactionBuilder => DeployTransaction {
+        required: { class_hash: ClassHash },
+        optional: {
+            version: TransactionVersion,
+            contract_address_salt: ContractAddressSalt,
+            constructor_calldata: Calldata,
+        },
+        hash: calculate_deploy_transaction_hash,
+        deployed_by: ContractAddress::default(),
+    }
+);
+
+transaction_builder!(
+    /// A builder of an [`InvokeTransactionV0`].
+    InvokeTransactionV0Builder => InvokeTransactionV0 {
+        required: {
+            sender_address: ContractAddress,
+            entry_point_selector: EntryPointSelector,
+            max_fee: Fee,
+        },
+        optional: { nonce: Nonce, signature: TransactionSignature, calldata: Calldata },
+        hash: calculate_invoke_v0_transaction_hash,
+    }
+);
+signed_transaction_builder!(InvokeTransactionV0Builder => InvokeTransactionV0);
+
+transaction_builder!(
+    /// A builder of an [`InvokeTransactionV1`].
+    InvokeTransactionV1Builder => InvokeTransactionV1 {
+        required: { sender_address: ContractAddress, max_fee: Fee },
+        optional: { nonce: Nonce, signature: TransactionSignature, calldata: Calldata },
+        hash: calculate_invoke_v1_transaction_hash,
+    }
+);
+signed_transaction_builder!(InvokeTransactionV1Builder => InvokeTransactionV1);
+
+transaction_builder!(
+    /// A builder of an [`InvokeTransactionV3`].
+    InvokeTransactionV3Builder => InvokeTransactionV3 {
+        required: { sender_address: ContractAddress, resource_bounds: ResourceBoundsMapping },
+        optional: {
+            nonce: Nonce,
+            signature: TransactionSignature,
+            calldata: Calldata,
+            tip: Tip,
+            nonce_data_availability_mode: DataAvailabilityMode,
+            fee_data_availability_mode: DataAvailabilityMode,
+            paymaster_data: PaymasterData,
+            account_deployment_data: AccountDeploymentData,
+        },
+        hash: calculate_invoke_v3_transaction_hash,
+    }
+);
+signed_transaction_builder!(InvokeTransactionV3Builder => InvokeTransactionV3);
+
+transaction_builder!(
+    /// A builder of an [`L1HandlerTransaction`].
+    L1HandlerTransactionBuilder => L1HandlerTransaction {
+        required: {
+            contract_address: ContractAddress,
+            entry_point_selector: EntryPointSelector,
+        },
+        optional: { version: TransactionVersion, nonce: Nonce, calldata: Calldata },
+        hash: calculate_l1_handler_transaction_hash,
+    }
+);
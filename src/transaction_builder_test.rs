@@ -0,0 +1,93 @@
+use assert_matches::assert_matches;
+
+use crate::core::{
+    calculate_contract_address, ChainId, ClassHash, ContractAddress, Nonce, PatriciaKey,
+};
+use crate::crypto::{verify_transaction_signature, PrivateKeySigner, TransactionSigner};
+use crate::hash::{Pedersen, StarkFelt, StarkHash};
+use crate::transaction::{
+    Calldata, ContractAddressSalt, DeployAccountTransaction, Fee, InvokeTransaction, Transaction,
+};
+use crate::transaction_builder::{DeployAccountTransactionV1Builder, InvokeTransactionV1Builder};
+use crate::transaction_hash::{validate_transaction_hash, TransactionOptions};
+use crate::{calldata, patricia_key, stark_felt, StarknetApiError};
+
+fn chain_id() -> ChainId {
+    ChainId("SN_MAIN".to_string())
+}
+
+#[test]
+fn build_invoke_transaction() {
+    let tx = InvokeTransactionV1Builder::default()
+        .sender_address(ContractAddress(patricia_key!("0x123")))
+        .max_fee(Fee(1000))
+        .nonce(Nonce(stark_felt!("0x1")))
+        .calldata(calldata![stark_felt!("0x2")])
+        .build(&chain_id())
+        .unwrap();
+    assert_eq!(tx.max_fee, Fee(1000));
+    assert_eq!(tx.calldata, calldata![stark_felt!("0x2")]);
+    let tx = Transaction::Invoke(InvokeTransaction::V1(tx));
+    assert!(validate_transaction_hash(&tx, &chain_id(), &TransactionOptions::default()).unwrap());
+}
+
+#[test]
+fn missing_required_field() {
+    let result = InvokeTransactionV1Builder::default()
+        .sender_address(ContractAddress(patricia_key!("0x123")))
+        .build(&chain_id());
+    assert_matches!(result, Err(StarknetApiError::MissingTransactionField { field: "max_fee" }));
+}
+
+#[test]
+fn build_and_sign_deploy_account_transaction() {
+    let signer = PrivateKeySigner::new(stark_felt!("0x1234"));
+    let class_hash = ClassHash(stark_felt!("0x10"));
+    let salt = ContractAddressSalt(stark_felt!("0x20"));
+    let constructor_calldata = calldata![signer.public_key().0];
+    let tx = DeployAccountTransactionV1Builder::default()
+        .class_hash(class_hash)
+        .max_fee(Fee(1000))
+        .contract_address_salt(salt)
+        .constructor_calldata(constructor_calldata.clone())
+        .build_and_sign(&chain_id(), &signer)
+        .unwrap();
+
+    let expected_address = calculate_contract_address::<Pedersen>(
+        salt,
+        class_hash,
+        &constructor_calldata,
+        ContractAddress::default(),
+    )
+    .unwrap();
+    assert_eq!(tx.contract_address, expected_address);
+    assert!(
+        verify_transaction_signature(&signer.public_key(), &tx.transaction_hash, &tx.signature)
+            .unwrap()
+    );
+    let tx = Transaction::DeployAccount(DeployAccountTransaction::V1(tx));
+    assert!(validate_transaction_hash(&tx, &chain_id(), &TransactionOptions::default()).unwrap());
+}
+
+#[test]
+fn build_and_sign_query_transaction() {
+    let signer = PrivateKeySigner::new(stark_felt!("0x1234"));
+    let builder = InvokeTransactionV1Builder::default()
+        .sender_address(ContractAddress(patricia_key!("0x123")))
+        .max_fee(Fee(1000))
+        .calldata(calldata![stark_felt!("0x2")]);
+    let options = TransactionOptions { only_query: true };
+    let query =
+        builder.clone().build_and_sign_with_options(&chain_id(), &options, &signer).unwrap();
+    assert_ne!(query.transaction_hash, builder.build(&chain_id()).unwrap().transaction_hash);
+    assert!(
+        verify_transaction_signature(
+            &signer.public_key(),
+            &query.transaction_hash,
+            &query.signature
+        )
+        .unwrap()
+    );
+    let query = Transaction::Invoke(InvokeTransaction::V1(query));
+    assert!(validate_transaction_hash(&query, &chain_id(), &options).unwrap());
+}
@@ -0,0 +1,430 @@
+#[cfg(test)]
+#[path = "transaction_hash_test.rs"]
+mod transaction_hash_test;
+
+use once_cell::sync::Lazy;
+
+use crate::core::ChainId;
+use crate::hash::{pedersen_hash_array, poseidon_hash_many, StarkFelt};
+use crate::transaction::{
+    DataAvailabilityMode, DeclareTransaction, DeclareTransactionV0V1, DeclareTransactionV2,
+    DeclareTransactionV3, DeployAccountTransaction, DeployAccountTransactionV1,
+    DeployAccountTransactionV3, DeployTransaction, InvokeTransaction, InvokeTransactionV0,
+    InvokeTransactionV1, InvokeTransactionV3, L1HandlerTransaction, Resource,
+    ResourceBoundsMapping, Tip, Transaction, TransactionHash, TransactionVersion,
+};
+use crate::StarknetApiError;
+
+static DECLARE: Lazy<StarkFelt> = Lazy::new(|| ascii_as_felt("declare"));
+static DEPLOY: Lazy<StarkFelt> = Lazy::new(|| ascii_as_felt("deploy"));
+static DEPLOY_ACCOUNT: Lazy<StarkFelt> = Lazy::new(|| ascii_as_felt("deploy_account"));
+static INVOKE: Lazy<StarkFelt> = Lazy::new(|| ascii_as_felt("invoke"));
+static L1_HANDLER: Lazy<StarkFelt> = Lazy::new(|| ascii_as_felt("l1_handler"));
+
+/// The selector of the `constructor` entry point, used in the hash of deploy transactions.
+pub const CONSTRUCTOR_ENTRY_POINT_SELECTOR: &str =
+    "0x28ffe4ff0f226a9107253e17a904099aa4f63a02a5621de0576e5aa71bc5194";
+
+static CONSTRUCTOR_SELECTOR: Lazy<StarkFelt> = Lazy::new(|| {
+    StarkFelt::try_from(CONSTRUCTOR_ENTRY_POINT_SELECTOR)
+        .expect("The constructor selector should be a valid StarkFelt.")
+});
+
+/// Options of the hash of a transaction.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct TransactionOptions {
+    /// Whether the transaction is only simulated, as when estimating its fee. The version in the
+    /// hash of such a transaction is its version plus 2^128, so that its signature cannot be used
+    /// to execute it. Only declare, deploy account and invoke transactions can be queries.
+    pub only_query: bool,
+}
+
+impl TransactionOptions {
+    /// Returns the version that is part of the hash of a transaction of the given version.
+    pub fn signed_version(&self, version: TransactionVersion) -> TransactionVersion {
+        if self.only_query { version.with_query_bit() } else { version }
+    }
+}
+
+// Fails if the options are of a query, for transactions that are not sent by an account.
+fn reject_query(
+    version: TransactionVersion,
+    options: &TransactionOptions,
+) -> Result<(), StarknetApiError> {
+    if options.only_query {
+        return Err(StarknetApiError::InvalidTransactionVersion {
+            version: version.with_query_bit().0,
+            expected: version.0,
+        });
+    }
+    Ok(())
+}
+
+fn ascii_as_felt(ascii_str: &str) -> StarkFelt {
+    StarkFelt::try_from(format!("0x{}", hex::encode(ascii_str)).as_str())
+        .unwrap_or_else(|_| panic!("Failed to convert {ascii_str} to StarkFelt."))
+}
+
+fn chain_id_as_felt(chain_id: &ChainId) -> Result<StarkFelt, StarknetApiError> {
+    StarkFelt::try_from(chain_id.as_hex().as_str())
+}
+
+/// Calculates the hash of a [`Transaction`] on the given chain, as defined in
+/// <https://docs.starknet.io/documentation/architecture_and_concepts/Network_Architecture/transactions/#transaction_hash>.
+pub fn calculate_transaction_hash(
+    transaction: &Transaction,
+    chain_id: &ChainId,
+    options: &TransactionOptions,
+) -> Result<TransactionHash, StarknetApiError> {
+    match transaction {
+        Transaction::Declare(DeclareTransaction::V0(tx)) => {
+            calculate_declare_v0_transaction_hash(tx, chain_id, options)
+        }
+        Transaction::Declare(DeclareTransaction::V1(tx)) => {
+            calculate_declare_v1_transaction_hash(tx, chain_id, options)
+        }
+        Transaction::Declare(DeclareTransaction::V2(tx)) => {
+            calculate_declare_v2_transaction_hash(tx, chain_id, options)
+        }
+        Transaction::Declare(DeclareTransaction::V3(tx)) => {
+            calculate_declare_v3_transaction_hash(tx, chain_id, options)
+        }
+        Transaction::Deploy(tx) => calculate_deploy_transaction_hash(tx, chain_id, options),
+        Transaction::DeployAccount(DeployAccountTransaction::V1(tx)) => {
+            calculate_deploy_account_v1_transaction_hash(tx, chain_id, options)
+        }
+        Transaction::DeployAccount(DeployAccountTransaction::V3(tx)) => {
+            calculate_deploy_account_v3_transaction_hash(tx, chain_id, options)
+        }
+        Transaction::Invoke(InvokeTransaction::V0(tx)) => {
+            calculate_invoke_v0_transaction_hash(tx, chain_id, options)
+        }
+        Transaction::Invoke(InvokeTransaction::V1(tx)) => {
+            calculate_invoke_v1_transaction_hash(tx, chain_id, options)
+        }
+        Transaction::Invoke(InvokeTransaction::V3(tx)) => {
+            calculate_invoke_v3_transaction_hash(tx, chain_id, options)
+        }
+        Transaction::L1Handler(tx) => calculate_l1_handler_transaction_hash(tx, chain_id, options),
+    }
+}
+
+/// Returns true if the stored hash of the transaction matches its calculated hash.
+/// Transactions from old blocks are also checked against the deprecated hash formulas.
+pub fn validate_transaction_hash(
+    transaction: &Transaction,
+    chain_id: &ChainId,
+    options: &TransactionOptions,
+) -> Result<bool, StarknetApiError> {
+    let expected_hash = transaction.transaction_hash();
+    if calculate_transaction_hash(transaction, chain_id, options)? == expected_hash {
+        return Ok(true);
+    }
+    // Query transactions were never hashed with the deprecated formulas.
+    if options.only_query {
+        return Ok(false);
+    }
+    Ok(calculate_deprecated_transaction_hashes(transaction, chain_id)?.contains(&expected_hash))
+}
+
+/// Calculates the hashes a transaction could have had under the formulas used before the
+/// transaction version was part of the hash.
+pub fn calculate_deprecated_transaction_hashes(
+    transaction: &Transaction,
+    chain_id: &ChainId,
+) -> Result<Vec<TransactionHash>, StarknetApiError> {
+    let chain_id = chain_id_as_felt(chain_id)?;
+    Ok(match transaction {
+        Transaction::Deploy(tx) => vec![TransactionHash(pedersen_hash_array(&[
+            *DEPLOY,
+            *tx.contract_address.0.key(),
+            *CONSTRUCTOR_SELECTOR,
+            pedersen_hash_array(&tx.constructor_calldata.0),
+            chain_id,
+        ]))],
+        Transaction::Invoke(InvokeTransaction::V0(tx)) => {
+            vec![TransactionHash(pedersen_hash_array(&[
+                *INVOKE,
+                *tx.sender_address.0.key(),
+                tx.entry_point_selector.0,
+                pedersen_hash_array(&tx.calldata.0),
+                chain_id,
+            ]))]
+        }
+        Transaction::L1Handler(tx) => {
+            let without_nonce = [
+                *L1_HANDLER,
+                *tx.contract_address.0.key(),
+                tx.entry_point_selector.0,
+                pedersen_hash_array(&tx.calldata.0),
+                chain_id,
+            ];
+            let mut with_nonce = without_nonce.to_vec();
+            with_nonce.push(tx.nonce.0);
+            vec![
+                TransactionHash(pedersen_hash_array(&without_nonce)),
+                TransactionHash(pedersen_hash_array(&with_nonce)),
+            ]
+        }
+        _ => vec![],
+    })
+}
+
+/// Calculates the hash of a declare V0 transaction.
+pub fn calculate_declare_v0_transaction_hash(
+    transaction: &DeclareTransactionV0V1,
+    chain_id: &ChainId,
+    options: &TransactionOptions,
+) -> Result<TransactionHash, StarknetApiError> {
+    Ok(TransactionHash(pedersen_hash_array(&[
+        *DECLARE,
+        options.signed_version(TransactionVersion::ZERO).0,
+        *transaction.sender_address.0.key(),
+        StarkFelt::from(0_u8),
+        pedersen_hash_array(&[]),
+        transaction.max_fee.into(),
+        chain_id_as_felt(chain_id)?,
+        transaction.class_hash.0,
+    ])))
+}
+
+/// Calculates the hash of a declare V1 transaction.
+pub fn calculate_declare_v1_transaction_hash(
+    transaction: &DeclareTransactionV0V1,
+    chain_id: &ChainId,
+    options: &TransactionOptions,
+) -> Result<TransactionHash, StarknetApiError> {
+    Ok(TransactionHash(pedersen_hash_array(&[
+        *DECLARE,
+        options.signed_version(TransactionVersion::ONE).0,
+        *transaction.sender_address.0.key(),
+        StarkFelt::from(0_u8),
+        pedersen_hash_array(&[transaction.class_hash.0]),
+        transaction.max_fee.into(),
+        chain_id_as_felt(chain_id)?,
+        transaction.nonce.0,
+    ])))
+}
+
+/// Calculates the hash of a declare V2 transaction.
+pub fn calculate_declare_v2_transaction_hash(
+    transaction: &DeclareTransactionV2,
+    chain_id: &ChainId,
+    options: &TransactionOptions,
+) -> Result<TransactionHash, StarknetApiError> {
+    Ok(TransactionHash(pedersen_hash_array(&[
+        *DECLARE,
+        options.signed_version(TransactionVersion::TWO).0,
+        *transaction.sender_address.0.key(),
+        StarkFelt::from(0_u8),
+        pedersen_hash_array(&[transaction.class_hash.0]),
+        transaction.max_fee.into(),
+        chain_id_as_felt(chain_id)?,
+        transaction.nonce.0,
+        transaction.compiled_class_hash.0,
+    ])))
+}
+
+/// Calculates the hash of a declare V3 transaction.
+pub fn calculate_declare_v3_transaction_hash(
+    transaction: &DeclareTransactionV3,
+    chain_id: &ChainId,
+    options: &TransactionOptions,
+) -> Result<TransactionHash, StarknetApiError> {
+    Ok(TransactionHash(poseidon_hash_many(&[
+        *DECLARE,
+        options.signed_version(TransactionVersion::THREE).0,
+        *transaction.sender_address.0.key(),
+        hash_fee_fields(transaction.tip, &transaction.resource_bounds),
+        poseidon_hash_many(&transaction.paymaster_data.0),
+        chain_id_as_felt(chain_id)?,
+        transaction.nonce.0,
+        data_availability_modes_as_felt(
+            transaction.nonce_data_availability_mode,
+            transaction.fee_data_availability_mode,
+        ),
+        poseidon_hash_many(&transaction.account_deployment_data.0),
+        transaction.class_hash.0,
+        transaction.compiled_class_hash.0,
+    ])))
+}
+
+/// Calculates the hash of a deploy account V1 transaction.
+pub fn calculate_deploy_account_v1_transaction_hash(
+    transaction: &DeployAccountTransactionV1,
+    chain_id: &ChainId,
+    options: &TransactionOptions,
+) -> Result<TransactionHash, StarknetApiError> {
+    let calldata = [transaction.class_hash.0, transaction.contract_address_salt.0]
+        .into_iter()
+        .chain(transaction.constructor_calldata.0.iter().copied())
+        .collect::<Vec<_>>();
+    Ok(TransactionHash(pedersen_hash_array(&[
+        *DEPLOY_ACCOUNT,
+        options.signed_version(transaction.version).0,
+        *transaction.contract_address.0.key(),
+        StarkFelt::from(0_u8),
+        pedersen_hash_array(&calldata),
+        transaction.max_fee.into(),
+        chain_id_as_felt(chain_id)?,
+        transaction.nonce.0,
+    ])))
+}
+
+/// Calculates the hash of a deploy account V3 transaction.
+pub fn calculate_deploy_account_v3_transaction_hash(
+    transaction: &DeployAccountTransactionV3,
+    chain_id: &ChainId,
+    options: &TransactionOptions,
+) -> Result<TransactionHash, StarknetApiError> {
+    Ok(TransactionHash(poseidon_hash_many(&[
+        *DEPLOY_ACCOUNT,
+        options.signed_version(TransactionVersion::THREE).0,
+        *transaction.contract_address.0.key(),
+        hash_fee_fields(transaction.tip, &transaction.resource_bounds),
+        poseidon_hash_many(&transaction.paymaster_data.0),
+        chain_id_as_felt(chain_id)?,
+        transaction.nonce.0,
+        data_availability_modes_as_felt(
+            transaction.nonce_data_availability_mode,
+            transaction.fee_data_availability_mode,
+        ),
+        poseidon_hash_many(&transaction.constructor_calldata.0),
+        transaction.class_hash.0,
+        transaction.contract_address_salt.0,
+    ])))
+}
+
+/// Calculates the hash of a deploy transaction.
+pub fn calculate_deploy_transaction_hash(
+    transaction: &DeployTransaction,
+    chain_id: &ChainId,
+    options: &TransactionOptions,
+) -> Result<TransactionHash, StarknetApiError> {
+    reject_query(transaction.version, options)?;
+    Ok(TransactionHash(pedersen_hash_array(&[
+        *DEPLOY,
+        transaction.version.0,
+        *transaction.contract_address.0.key(),
+        *CONSTRUCTOR_SELECTOR,
+        pedersen_hash_array(&transaction.constructor_calldata.0),
+        // No fee in deploy transaction.
+        StarkFelt::from(0_u8),
+        chain_id_as_felt(chain_id)?,
+    ])))
+}
+
+/// Calculates the hash of an invoke V0 transaction.
+pub fn calculate_invoke_v0_transaction_hash(
+    transaction: &InvokeTransactionV0,
+    chain_id: &ChainId,
+    options: &TransactionOptions,
+) -> Result<TransactionHash, StarknetApiError> {
+    Ok(TransactionHash(pedersen_hash_array(&[
+        *INVOKE,
+        options.signed_version(TransactionVersion::ZERO).0,
+        *transaction.sender_address.0.key(),
+        transaction.entry_point_selector.0,
+        pedersen_hash_array(&transaction.calldata.0),
+        transaction.max_fee.into(),
+        chain_id_as_felt(chain_id)?,
+    ])))
+}
+
+/// Calculates the hash of an invoke V1 transaction.
+pub fn calculate_invoke_v1_transaction_hash(
+    transaction: &InvokeTransactionV1,
+    chain_id: &ChainId,
+    options: &TransactionOptions,
+) -> Result<TransactionHash, StarknetApiError> {
+    Ok(TransactionHash(pedersen_hash_array(&[
+        *INVOKE,
+        options.signed_version(TransactionVersion::ONE).0,
+        *transaction.sender_address.0.key(),
+        // No entry point selector in invoke V1 transaction.
+        StarkFelt::from(0_u8),
+        pedersen_hash_array(&transaction.calldata.0),
+        transaction.max_fee.into(),
+        chain_id_as_felt(chain_id)?,
+        transaction.nonce.0,
+    ])))
+}
+
+/// Calculates the hash of an invoke V3 transaction.
+pub fn calculate_invoke_v3_transaction_hash(
+    transaction: &InvokeTransactionV3,
+    chain_id: &ChainId,
+    options: &TransactionOptions,
+) -> Result<TransactionHash, StarknetApiError> {
+    Ok(TransactionHash(poseidon_hash_many(&[
+        *INVOKE,
+        options.signed_version(TransactionVersion::THREE).0,
+        *transaction.sender_address.0.key(),
+        hash_fee_fields(transaction.tip, &transaction.resource_bounds),
+        poseidon_hash_many(&transaction.paymaster_data.0),
+        chain_id_as_felt(chain_id)?,
+        transaction.nonce.0,
+        data_availability_modes_as_felt(
+            transaction.nonce_data_availability_mode,
+            transaction.fee_data_availability_mode,
+        ),
+        poseidon_hash_many(&transaction.account_deployment_data.0),
+        poseidon_hash_many(&transaction.calldata.0),
+    ])))
+}
+
+/// Calculates the hash of an L1 handler transaction.
+pub fn calculate_l1_handler_transaction_hash(
+    transaction: &L1HandlerTransaction,
+    chain_id: &ChainId,
+    options: &TransactionOptions,
+) -> Result<TransactionHash, StarknetApiError> {
+    reject_query(transaction.version, options)?;
+    Ok(TransactionHash(pedersen_hash_array(&[
+        *L1_HANDLER,
+        transaction.version.0,
+        *transaction.contract_address.0.key(),
+        transaction.entry_point_selector.0,
+        pedersen_hash_array(&transaction.calldata.0),
+        // No fee in L1 handler transaction.
+        StarkFelt::from(0_u8),
+        chain_id_as_felt(chain_id)?,
+        transaction.nonce.0,
+    ])))
+}
+
+// Hashes the tip and the bounds of L1 gas and L2 gas, each bound packed into a single felt.
+fn hash_fee_fields(tip: Tip, resource_bounds: &ResourceBoundsMapping) -> StarkFelt {
+    poseidon_hash_many(&[
+        tip.into(),
+        resource_bounds_as_felt(resource_bounds, Resource::L1Gas, b"L1_GAS"),
+        resource_bounds_as_felt(resource_bounds, Resource::L2Gas, b"L2_GAS"),
+    ])
+}
+
+// Packs the bounds of a resource as `resource_name << 192 | max_amount << 128 |
+// max_price_per_unit`, where the name is a short string of at most 8 characters.
+fn resource_bounds_as_felt(
+    resource_bounds: &ResourceBoundsMapping,
+    resource: Resource,
+    resource_name: &[u8],
+) -> StarkFelt {
+    let bounds = resource_bounds.get(resource);
+    let mut bytes = [0u8; 32];
+    bytes[8 - resource_name.len()..8].copy_from_slice(resource_name);
+    bytes[8..16].copy_from_slice(&bounds.max_amount.to_be_bytes());
+    bytes[16..].copy_from_slice(&bounds.max_price_per_unit.to_be_bytes());
+    StarkFelt::new(bytes).expect("Resource bounds should fit in a StarkFelt.")
+}
+
+// Packs the data availability modes as `nonce_mode << 32 | fee_mode`.
+fn data_availability_modes_as_felt(
+    nonce_mode: DataAvailabilityMode,
+    fee_mode: DataAvailabilityMode,
+) -> StarkFelt {
+    let as_u64 = |mode| match mode {
+        DataAvailabilityMode::L1 => 0_u64,
+        DataAvailabilityMode::L2 => 1_u64,
+    };
+    StarkFelt::from(as_u64(nonce_mode) << 32 | as_u64(fee_mode))
+}
@@ -0,0 +1,176 @@
+use assert_matches::assert_matches;
+use serde::Deserialize;
+
+use crate::core::{ChainId, ContractAddress, EntryPointSelector, Nonce, PatriciaKey};
+use crate::hash::{pedersen_hash_array, StarkFelt, StarkHash};
+use crate::test_utils::read_json_file;
+use crate::transaction::{
+    Calldata, Fee, InvokeTransaction, InvokeTransactionV1, L1HandlerTransaction, Transaction,
+    TransactionHash, TransactionVersion,
+};
+use crate::transaction_hash::{
+    calculate_deprecated_transaction_hashes, calculate_invoke_v1_transaction_hash,
+    calculate_transaction_hash, validate_transaction_hash, TransactionOptions,
+};
+use crate::{calldata, patricia_key, stark_felt, StarknetApiError};
+
+fn chain_id() -> ChainId {
+    ChainId("SN_MAIN".to_string())
+}
+
+#[derive(Debug, Deserialize)]
+struct TransactionHashTestData {
+    chain_id: ChainId,
+    transaction: Transaction,
+    only_query_transaction_hash: Option<TransactionHash>,
+}
+
+// The transactions were taken from Starknet mainnet. They can be found by hash in
+// https://alpha-mainnet.starknet.io/feeder_gateway/get_transaction?transactionHash=<hash>.
+#[test]
+fn transaction_hash() {
+    let test_data: Vec<TransactionHashTestData> =
+        serde_json::from_value(read_json_file("transaction_hash.json")).unwrap();
+    for data in test_data {
+        let actual = calculate_transaction_hash(
+            &data.transaction,
+            &data.chain_id,
+            &TransactionOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(actual, data.transaction.transaction_hash(), "{data:?}");
+    }
+}
+
+#[test]
+fn only_query_transaction_hash() {
+    let test_data: Vec<TransactionHashTestData> =
+        serde_json::from_value(read_json_file("transaction_hash.json")).unwrap();
+    let options = TransactionOptions { only_query: true };
+    for data in test_data {
+        let actual = calculate_transaction_hash(&data.transaction, &data.chain_id, &options);
+        match data.transaction {
+            // Only account transactions can be queries.
+            Transaction::Deploy(_) | Transaction::L1Handler(_) => {
+                assert_matches!(actual, Err(StarknetApiError::InvalidTransactionVersion { .. }));
+            }
+            _ => assert_eq!(actual.unwrap(), data.only_query_transaction_hash.unwrap(), "{data:?}"),
+        }
+    }
+}
+
+#[test]
+fn query_transaction_hash_signs_the_query_version() {
+    let tx = InvokeTransactionV1 {
+        max_fee: Fee(1),
+        nonce: Nonce(stark_felt!("0x1")),
+        sender_address: ContractAddress(patricia_key!("0x123")),
+        calldata: calldata![stark_felt!("0x1"), stark_felt!("0x2")],
+        ..Default::default()
+    };
+    let query_hash = calculate_invoke_v1_transaction_hash(
+        &tx,
+        &chain_id(),
+        &TransactionOptions { only_query: true },
+    )
+    .unwrap();
+    let expected = pedersen_hash_array(&[
+        stark_felt!("0x696e766f6b65"),
+        stark_felt!("0x100000000000000000000000000000001"),
+        stark_felt!("0x123"),
+        stark_felt!("0x0"),
+        pedersen_hash_array(&tx.calldata.0),
+        stark_felt!("0x1"),
+        stark_felt!("0x534e5f4d41494e"),
+        stark_felt!("0x1"),
+    ]);
+    assert_eq!(query_hash, TransactionHash(expected));
+
+    let query = Transaction::Invoke(InvokeTransaction::V1(InvokeTransactionV1 {
+        transaction_hash: query_hash,
+        ..tx
+    }));
+    assert!(
+        validate_transaction_hash(&query, &chain_id(), &TransactionOptions { only_query: true })
+            .unwrap()
+    );
+    assert!(
+        !validate_transaction_hash(&query, &chain_id(), &TransactionOptions::default()).unwrap()
+    );
+}
+
+#[test]
+fn deprecated_transaction_hash() {
+    let test_data: Vec<TransactionHashTestData> =
+        serde_json::from_value(read_json_file("deprecated_transaction_hash.json")).unwrap();
+    for data in test_data {
+        assert!(
+            validate_transaction_hash(
+                &data.transaction,
+                &data.chain_id,
+                &TransactionOptions::default()
+            )
+            .unwrap(),
+            "{data:?}"
+        );
+    }
+}
+
+#[test]
+fn validate_invoke_transaction_hash() {
+    let mut tx = InvokeTransactionV1 {
+        max_fee: Fee(1),
+        nonce: Nonce(stark_felt!("0x1")),
+        sender_address: ContractAddress(patricia_key!("0x123")),
+        calldata: calldata![stark_felt!("0x1"), stark_felt!("0x2")],
+        ..Default::default()
+    };
+    let invalid = Transaction::Invoke(InvokeTransaction::V1(tx.clone()));
+    assert!(
+        !validate_transaction_hash(&invalid, &chain_id(), &TransactionOptions::default()).unwrap()
+    );
+
+    tx.transaction_hash =
+        calculate_transaction_hash(&invalid, &chain_id(), &TransactionOptions::default()).unwrap();
+    let valid = Transaction::Invoke(InvokeTransaction::V1(tx));
+    assert!(
+        validate_transaction_hash(&valid, &chain_id(), &TransactionOptions::default()).unwrap()
+    );
+    // The hash depends on the chain.
+    assert!(
+        !validate_transaction_hash(
+            &valid,
+            &ChainId("SN_GOERLI".to_string()),
+            &TransactionOptions::default()
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn validate_deprecated_l1_handler_transaction_hash() {
+    let mut tx = L1HandlerTransaction {
+        version: TransactionVersion(StarkFelt::from(0_u8)),
+        nonce: Nonce(stark_felt!("0x7")),
+        contract_address: ContractAddress(patricia_key!("0x456")),
+        entry_point_selector: EntryPointSelector(stark_felt!("0x789")),
+        calldata: calldata![stark_felt!("0x1")],
+        ..Default::default()
+    };
+    let deprecated_hashes =
+        calculate_deprecated_transaction_hashes(&Transaction::L1Handler(tx.clone()), &chain_id())
+            .unwrap();
+    assert_eq!(deprecated_hashes.len(), 2);
+
+    for hash in deprecated_hashes {
+        tx.transaction_hash = hash;
+        assert!(
+            validate_transaction_hash(
+                &Transaction::L1Handler(tx.clone()),
+                &chain_id(),
+                &TransactionOptions::default()
+            )
+            .unwrap()
+        );
+    }
+}
@@ -0,0 +1,355 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use assert_matches::assert_matches;
+use primitive_types::H256;
+use serde_json::json;
+
+use crate::block::{GasPrice, GasPricePerToken};
+use crate::core::{ContractAddress, EntryPointSelector, Nonce, PatriciaKey};
+use crate::hash::{StarkFelt, StarkHash};
+use crate::transaction::{
+    Builtin, Calldata, DataAvailabilityMode, DataAvailabilityResources, DeployAccountTransaction,
+    DeployAccountTransactionV3, EthAddress, ExecutionResources, Fee, FeePayment, InvokeTransaction,
+    InvokeTransactionOutput, InvokeTransactionV3, L1HandlerTransaction, L1ToL2Payload,
+    L2ToL1Payload, MessageToL1, MessageToL2, PaymasterData, PriceUnit, Resource, ResourceBounds,
+    ResourceBoundsMapping, Tip, Transaction, TransactionExecutionStatus, TransactionOutput,
+    TransactionReceipt, TransactionVersion,
+};
+use crate::{calldata, patricia_key, stark_felt, StarknetApiError};
+
+#[test]
+fn invoke_v3_json_serde() {
+    let json = json!({
+        "transaction_hash": "0x1",
+        "resource_bounds": {
+            "l1_gas": { "max_amount": "0x186a0", "max_price_per_unit": "0x5af3107a4000" },
+            "l2_gas": { "max_amount": "0x0", "max_price_per_unit": "0x0" },
+        },
+        "tip": "0xa",
+        "signature": ["0x2", "0x3"],
+        "nonce": "0x4",
+        "sender_address": "0x5",
+        "calldata": ["0x6"],
+        "nonce_data_availability_mode": "L1",
+        "fee_data_availability_mode": "L2",
+        "paymaster_data": ["0x7"],
+        "account_deployment_data": [],
+    });
+    let tx: InvokeTransactionV3 = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(
+        tx.resource_bounds,
+        ResourceBoundsMapping(BTreeMap::from([
+            (
+                Resource::L1Gas,
+                ResourceBounds { max_amount: 100000, max_price_per_unit: 100000000000000 },
+            ),
+            (Resource::L2Gas, ResourceBounds::default()),
+        ]))
+    );
+    assert_eq!(tx.tip, Tip(10));
+    assert_eq!(tx.nonce, Nonce(stark_felt!("0x4")));
+    assert_eq!(tx.sender_address, ContractAddress(patricia_key!("0x5")));
+    assert_eq!(tx.calldata, calldata![stark_felt!("0x6")]);
+    assert_eq!(tx.nonce_data_availability_mode, DataAvailabilityMode::L1);
+    assert_eq!(tx.fee_data_availability_mode, DataAvailabilityMode::L2);
+    assert_eq!(tx.paymaster_data, PaymasterData(vec![stark_felt!("0x7")]));
+    assert_eq!(serde_json::to_value(&tx).unwrap(), json);
+
+    let tx = InvokeTransaction::V3(tx);
+    assert_eq!(tx.version(), TransactionVersion(stark_felt!("0x3")));
+    assert_eq!(tx.max_fee(), None);
+}
+
+#[test]
+fn deploy_account_json_serde() {
+    // A deploy account transaction from before V3 is serialized without a version tag.
+    let json = json!({
+        "DeployAccount": {
+            "transaction_hash": "0x1",
+            "max_fee": "0x2",
+            "version": "0x1",
+            "signature": ["0x3"],
+            "nonce": "0x0",
+            "class_hash": "0x4",
+            "contract_address": "0x5",
+            "contract_address_salt": "0x6",
+            "constructor_calldata": ["0x7"],
+        }
+    });
+    let tx: Transaction = serde_json::from_value(json.clone()).unwrap();
+    let Transaction::DeployAccount(DeployAccountTransaction::V1(v1_tx)) = &tx else {
+        panic!("Expected a deploy account V1 transaction, got {tx:?}.");
+    };
+    assert_eq!(v1_tx.max_fee, Fee(2));
+    assert_eq!(tx.version(), TransactionVersion::ONE);
+    assert_eq!(serde_json::to_value(&tx).unwrap(), json);
+
+    let v3_tx = Transaction::DeployAccount(DeployAccountTransaction::V3(
+        DeployAccountTransactionV3::default(),
+    ));
+    let v3_json = serde_json::to_value(&v3_tx).unwrap();
+    assert!(v3_json["DeployAccount"].get("resource_bounds").is_some());
+    assert_eq!(serde_json::from_value::<Transaction>(v3_json).unwrap(), v3_tx);
+}
+
+#[test]
+fn transaction_output_execution_status_json_serde() {
+    let json = json!({
+        "actual_fee": { "amount": "0x1", "unit": "FRI" },
+        "messages_sent": [],
+        "events": [],
+        "execution_status": "REVERTED",
+        "revert_reason": "Insufficient balance.",
+        "execution_resources": {
+            "steps": 10,
+            "memory_holes": 0,
+            "data_availability": { "l1_gas": 0, "l1_data_gas": 0 },
+        },
+    });
+    let output: InvokeTransactionOutput = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(
+        output.execution_status,
+        TransactionExecutionStatus::Reverted { reason: "Insufficient balance.".to_string() }
+    );
+    assert_eq!(serde_json::to_value(&output).unwrap(), json);
+
+    let output = TransactionOutput::Invoke(InvokeTransactionOutput::default());
+    assert_eq!(output.execution_status(), &TransactionExecutionStatus::Succeeded);
+    assert_eq!(serde_json::to_value(&output).unwrap()["Invoke"]["execution_status"], "SUCCEEDED");
+
+    let mut invalid_json = json;
+    invalid_json.as_object_mut().unwrap().remove("revert_reason");
+    assert!(serde_json::from_value::<InvokeTransactionOutput>(invalid_json).is_err());
+}
+
+#[test]
+fn receipt_without_execution_status() {
+    // A receipt from before Starknet v0.12.1, when transactions could not be reverted.
+    let json = json!({
+        "transaction_hash": "0x1",
+        "block_hash": "0x2",
+        "block_number": 3,
+        "Invoke": {
+            "actual_fee": { "amount": "0x4", "unit": "WEI" },
+            "messages_sent": [],
+            "events": [],
+            "execution_resources": { "steps": 5 },
+        },
+    });
+    let receipt: TransactionReceipt = serde_json::from_value(json).unwrap();
+    assert_eq!(receipt.output.execution_status(), &TransactionExecutionStatus::Succeeded);
+}
+
+#[test]
+fn execution_resources_json_serde() {
+    let json = json!({
+        "steps": 100,
+        "memory_holes": 3,
+        "range_check_builtin_applications": 5,
+        "segment_arena_builtin": 1,
+        "data_availability": { "l1_gas": 0, "l1_data_gas": 128 },
+    });
+    let resources: ExecutionResources = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(
+        resources,
+        ExecutionResources {
+            steps: 100,
+            memory_holes: 3,
+            builtin_instance_counter: BTreeMap::from([
+                (Builtin::RangeCheck, 5),
+                (Builtin::SegmentArena, 1),
+            ]),
+            data_availability: DataAvailabilityResources { l1_gas: 0, l1_data_gas: 128 },
+        }
+    );
+    assert_eq!(serde_json::to_value(&resources).unwrap(), json);
+
+    // Memory holes and data availability are optional.
+    let resources: ExecutionResources = serde_json::from_value(json!({ "steps": 7 })).unwrap();
+    assert_eq!(resources, ExecutionResources { steps: 7, ..Default::default() });
+}
+
+#[test]
+fn feeder_gateway_execution_resources() {
+    let json = json!({
+        "n_steps": 100,
+        "n_memory_holes": 3,
+        "builtin_instance_counter": {
+            "range_check_builtin": 5,
+            "segment_arena_builtin": 1,
+            "range_check96_builtin": 2,
+        },
+        "data_availability": { "l1_gas": 0, "l1_data_gas": 128 },
+        "total_gas_consumed": { "l1_gas": 10, "l1_data_gas": 128 },
+    });
+    let resources: ExecutionResources = serde_json::from_value(json).unwrap();
+    assert_eq!(
+        resources,
+        ExecutionResources {
+            steps: 100,
+            memory_holes: 3,
+            builtin_instance_counter: BTreeMap::from([
+                (Builtin::RangeCheck, 5),
+                (Builtin::SegmentArena, 1),
+                (Builtin::RangeCheck96, 2),
+            ]),
+            data_availability: DataAvailabilityResources { l1_gas: 0, l1_data_gas: 128 },
+        }
+    );
+}
+
+#[test]
+fn unknown_builtin() {
+    let json =
+        json!({ "steps": 7, "add_mod_builtin_applications": 1, "new_builtin_applications": 2 });
+    let resources: ExecutionResources = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(resources.builtin_count(Builtin::AddMod), 1);
+    assert_eq!(resources.builtin_count(Builtin::Other("new".to_owned())), 2);
+    assert_eq!(serde_json::to_value(&resources).unwrap()["new_builtin_applications"], json!(2));
+}
+
+#[test]
+fn output_without_execution_resources() {
+    let json = json!({
+        "actual_fee": { "amount": "0x1", "unit": "WEI" },
+        "messages_sent": [],
+        "events": [],
+    });
+    let output: InvokeTransactionOutput = serde_json::from_value(json).unwrap();
+    assert_eq!(output.execution_resources, ExecutionResources::default());
+}
+
+#[test]
+fn execution_resources_sum() {
+    let first = ExecutionResources {
+        steps: 10,
+        memory_holes: 1,
+        builtin_instance_counter: BTreeMap::from([(Builtin::Pedersen, 2), (Builtin::Keccak, 1)]),
+        data_availability: DataAvailabilityResources { l1_gas: 5, l1_data_gas: 0 },
+    };
+    let second = ExecutionResources {
+        steps: 20,
+        memory_holes: 0,
+        builtin_instance_counter: BTreeMap::from([(Builtin::Pedersen, 3)]),
+        data_availability: DataAvailabilityResources { l1_gas: 0, l1_data_gas: 64 },
+    };
+    let total: ExecutionResources = [first, second].iter().sum();
+    assert_eq!(
+        total,
+        ExecutionResources {
+            steps: 30,
+            memory_holes: 1,
+            builtin_instance_counter: BTreeMap::from([
+                (Builtin::Pedersen, 5),
+                (Builtin::Keccak, 1)
+            ]),
+            data_availability: DataAvailabilityResources { l1_gas: 5, l1_data_gas: 64 },
+        }
+    );
+    assert_eq!(total.builtin_count(Builtin::Pedersen), 5);
+    assert_eq!(total.builtin_count(Builtin::Poseidon), 0);
+}
+
+#[test]
+fn fee_payment_json_serde() {
+    let fee = FeePayment { amount: Fee(26), unit: PriceUnit::Fri };
+    let json = json!({ "amount": "0x1a", "unit": "FRI" });
+    assert_eq!(serde_json::to_value(fee).unwrap(), json);
+    assert_eq!(serde_json::from_value::<FeePayment>(json).unwrap(), fee);
+
+    // A fee without a unit is in Wei.
+    let legacy_fee: FeePayment = serde_json::from_value(json!("0x1a")).unwrap();
+    assert_eq!(legacy_fee, FeePayment { amount: Fee(26), unit: PriceUnit::Wei });
+}
+
+#[test]
+fn fee_payment_arithmetic() {
+    let wei = |amount| FeePayment { amount: Fee(amount), unit: PriceUnit::Wei };
+    let fri = |amount| FeePayment { amount: Fee(amount), unit: PriceUnit::Fri };
+    assert_eq!(wei(1).checked_add(wei(2)), Some(wei(3)));
+    assert_eq!(wei(1).checked_add(fri(2)), None);
+    assert_eq!(wei(u128::MAX).checked_add(wei(1)), None);
+
+    let gas_prices = GasPricePerToken { price_in_fri: GasPrice(30), price_in_wei: GasPrice(2) };
+    assert_eq!(FeePayment::from_gas(5, &gas_prices, PriceUnit::Fri), Some(fri(150)));
+    assert_eq!(FeePayment::from_gas(u128::MAX, &gas_prices, PriceUnit::Wei), None);
+    assert_eq!(wei(11).convert(&gas_prices, PriceUnit::Fri), Some(fri(150)));
+    assert_eq!(fri(150).convert(&gas_prices, PriceUnit::Wei), Some(wei(10)));
+    assert_eq!(fri(150).convert(&gas_prices, PriceUnit::Fri), Some(fri(150)));
+    assert_eq!(wei(1).convert(&GasPricePerToken::default(), PriceUnit::Fri), None);
+}
+
+#[test]
+fn query_transaction_version() {
+    assert_eq!(
+        TransactionVersion::THREE_QUERY.0,
+        stark_felt!("0x100000000000000000000000000000003")
+    );
+    assert!(TransactionVersion::ONE_QUERY.is_query());
+    assert!(!TransactionVersion::ONE.is_query());
+    assert_eq!(TransactionVersion::TWO_QUERY.without_query_bit(), TransactionVersion::TWO);
+    assert_eq!(TransactionVersion::TWO.without_query_bit(), TransactionVersion::TWO);
+}
+
+#[test]
+fn validate_transaction_version() {
+    let invoke = Transaction::Invoke(InvokeTransaction::V3(InvokeTransactionV3::default()));
+    assert_eq!(invoke.version(), TransactionVersion::THREE);
+    invoke.validate_version(TransactionVersion::THREE).unwrap();
+    invoke.validate_version(TransactionVersion::THREE_QUERY).unwrap();
+    assert_matches!(
+        invoke.validate_version(TransactionVersion::ONE),
+        Err(StarknetApiError::InvalidTransactionVersion { .. })
+    );
+
+    let l1_handler = Transaction::L1Handler(L1HandlerTransaction::default());
+    l1_handler.validate_version(TransactionVersion::ZERO).unwrap();
+    // Only account transactions can be queried.
+    assert_matches!(
+        l1_handler.validate_version(TransactionVersion::ZERO_QUERY),
+        Err(StarknetApiError::InvalidTransactionVersion { .. })
+    );
+}
+
+#[test]
+fn message_to_l1_hash() {
+    // Test vectors generated with an independent Keccak-256 implementation.
+    let message = MessageToL1 {
+        from_address: ContractAddress(patricia_key!("0x123")),
+        to_address: EthAddress::try_from(stark_felt!("0xabcdef")).unwrap(),
+        payload: L2ToL1Payload(vec![stark_felt!("0x1"), stark_felt!("0x2")]),
+    };
+    assert_eq!(
+        message.hash(),
+        H256::from_str("0xca1c9e979a08b2cbcd92cd35bd7e6a241ffba1a7ed4ae7a21f808136b3d7b427")
+            .unwrap()
+    );
+}
+
+#[test]
+fn l1_handler_message_hash() {
+    let tx = L1HandlerTransaction {
+        nonce: Nonce(stark_felt!("0x7")),
+        contract_address: ContractAddress(patricia_key!("0x123")),
+        entry_point_selector: EntryPointSelector(stark_felt!("0x456")),
+        calldata: calldata![stark_felt!("0xabcdef"), stark_felt!("0x1"), stark_felt!("0x2")],
+        ..Default::default()
+    };
+    let message = MessageToL2::try_from(&tx).unwrap();
+    assert_eq!(
+        message,
+        MessageToL2 {
+            from_address: EthAddress::try_from(stark_felt!("0xabcdef")).unwrap(),
+            payload: L1ToL2Payload(vec![stark_felt!("0x1"), stark_felt!("0x2")]),
+        }
+    );
+    let expected =
+        H256::from_str("0x7a7272ba2ddee2e70562f8bbdcd27f20162d278ff10c95e88569f0be2013761b")
+            .unwrap();
+    assert_eq!(message.hash(tx.nonce, tx.contract_address, tx.entry_point_selector), expected);
+    assert_eq!(tx.message_hash().unwrap(), expected);
+
+    let tx = L1HandlerTransaction::default();
+    assert_matches!(tx.message_hash(), Err(StarknetApiError::MissingL1SenderAddress));
+}